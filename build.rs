@@ -0,0 +1,39 @@
+use std::env;
+use std::process::Command;
+use std::str;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=RUSTC");
+    println!("cargo:rustc-check-cfg=cfg(derivative_debug_struct_non_exhaustive)");
+    println!("cargo:rustc-check-cfg=cfg(derivative_debug_tuple_non_exhaustive)");
+
+    let minor = match rustc_minor_version() {
+        Some(minor) => minor,
+        None => return,
+    };
+
+    // `DebugStruct::finish_non_exhaustive` is stable since 1.53
+    if minor >= 53 {
+        println!("cargo:rustc-cfg=derivative_debug_struct_non_exhaustive");
+    }
+
+    // `DebugTuple::finish_non_exhaustive` is stable since 1.83
+    if minor >= 83 {
+        println!("cargo:rustc-cfg=derivative_debug_tuple_non_exhaustive");
+    }
+}
+
+/// Return the minor version of the compiler, eg. `34` for `rustc 1.34.0`.
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC")?;
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = str::from_utf8(&output.stdout).ok()?;
+
+    let mut pieces = version.split('.');
+    if pieces.next() != Some("rustc 1") {
+        return None;
+    }
+
+    pieces.next()?.parse().ok()
+}
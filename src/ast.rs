@@ -10,12 +10,14 @@ pub struct Input<'a> {
     pub generics: &'a syn::Generics,
     pub ident: syn::Ident,
     pub span: proc_macro2::Span,
+    pub vis: &'a syn::Visibility,
 }
 
 #[derive(Debug)]
 pub enum Body<'a> {
     Enum(Vec<Variant<'a>>),
     Struct(Style, Vec<Field<'a>>),
+    Union(Vec<Field<'a>>),
 }
 
 #[derive(Debug)]
@@ -56,12 +58,8 @@ impl<'a> Input<'a> {
                 let (style, fields) = struct_from_ast(fields, errors)?;
                 Body::Struct(style, fields)
             }
-            syn::Data::Union(..) => {
-                errors.extend(
-                    syn::Error::new_spanned(item, "derivative does not support unions")
-                        .to_compile_error(),
-                );
-                return Err(());
+            syn::Data::Union(syn::DataUnion { ref fields, .. }) => {
+                Body::Union(fields_from_ast(&fields.named, errors)?)
             }
         };
 
@@ -71,6 +69,7 @@ impl<'a> Input<'a> {
             generics: &item.generics,
             ident: item.ident.clone(),
             span: item.span(),
+            vis: &item.vis,
         })
     }
 
@@ -78,7 +77,7 @@ impl<'a> Input<'a> {
     pub fn is_trivial_enum(&self) -> bool {
         match &self.body {
             Body::Enum(e) => e.iter().all(|v| v.is_unit()),
-            Body::Struct(..) => false,
+            Body::Struct(..) | Body::Union(..) => false,
         }
     }
 }
@@ -90,14 +89,14 @@ impl<'a> Body<'a> {
                 .iter()
                 .flat_map(|variant| variant.fields.iter())
                 .collect(),
-            Body::Struct(_, ref fields) => fields.iter().collect(),
+            Body::Struct(_, ref fields) | Body::Union(ref fields) => fields.iter().collect(),
         }
     }
 
     pub fn is_empty(&self) -> bool {
         match *self {
             Body::Enum(ref variants) => variants.is_empty(),
-            Body::Struct(_, ref fields) => fields.is_empty(),
+            Body::Struct(_, ref fields) | Body::Union(ref fields) => fields.is_empty(),
         }
     }
 }
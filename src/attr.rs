@@ -1,10 +1,13 @@
 use proc_macro2;
+use quote;
 use syn;
 use syn::spanned::Spanned;
 
 /// Represent the `derivative` attributes on the input type (`struct`/`enum`).
 #[derive(Debug, Default)]
 pub struct Input {
+    /// Whether `Builder` is present and its specific attributes.
+    pub builder: Option<InputBuilder>,
     /// Whether `Clone` is present and its specific attributes.
     pub clone: Option<InputClone>,
     /// Whether `Copy` is present and its specific attributes.
@@ -13,6 +16,8 @@ pub struct Input {
     pub debug: Option<InputDebug>,
     /// Whether `Default` is present and its specific attributes.
     pub default: Option<InputDefault>,
+    /// Whether `Display` is present and its specific attributes.
+    pub display: Option<InputDisplay>,
     /// Whether `Eq` is present and its specific attributes.
     pub eq: Option<InputEq>,
     /// Whether `Hash` is present and its specific attributes.
@@ -24,6 +29,8 @@ pub struct Input {
     /// Whether `Ord` is present and its specific attributes.
     pub ord: Option<InputOrd>,
     pub is_packed: bool,
+    /// The integer type given with `#[repr]`, if any.
+    pub repr_int: Option<syn::Ident>,
 }
 
 #[derive(Debug, Default)]
@@ -49,6 +56,13 @@ pub struct Field {
     ord: FieldOrd,
 }
 
+#[derive(Debug, Default)]
+/// Represent the `derivative(Builder(…))` attributes on an input.
+pub struct InputBuilder {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+}
+
 #[derive(Debug, Default)]
 /// Represent the `derivative(Clone(…))` attributes on an input.
 pub struct InputClone {
@@ -56,6 +70,8 @@ pub struct InputClone {
     bounds: Option<Vec<syn::WherePredicate>>,
     /// Whether the implementation should have an explicit `clone_from`.
     pub clone_from: bool,
+    /// Whether the implementation should copy the value with `*self`.
+    via_copy: bool,
 }
 
 #[derive(Debug, Default)]
@@ -70,8 +86,20 @@ pub struct InputCopy {
 pub struct InputDebug {
     /// The `bound` attribute if present and the corresponding bounds.
     bounds: Option<Vec<syn::WherePredicate>>,
+    /// The `format_with` attribute if present and the function formatting the whole type.
+    format_with: Option<WithFn>,
     /// Whether the type is marked `transparent`.
     pub transparent: bool,
+    /// The `name` attribute if present and the name to use in the output.
+    name: Option<syn::LitStr>,
+    /// Whether the output should show that some fields are ignored.
+    non_exhaustive: bool,
+    /// Whether variant names should be prefixed with the name of the enumeration.
+    qualified: bool,
+    /// Whether the discriminants of variants should be shown.
+    discriminant: bool,
+    /// Whether the fields of the type can be flattened into another type.
+    flattenable: bool,
 }
 
 #[derive(Debug, Default)]
@@ -79,8 +107,23 @@ pub struct InputDebug {
 pub struct InputDefault {
     /// The `bound` attribute if present and the corresponding bounds.
     bounds: Option<Vec<syn::WherePredicate>>,
+    /// Whether the type is marked with `const`, ie. it has a `DEFAULT` constant.
+    pub is_const: bool,
     /// Whether the type is marked with `new`.
     pub new: bool,
+    /// Whether the type is marked with `new="args"`, ie. `new` takes the fields without defaults.
+    pub new_with_args: bool,
+    /// The default value for the whole type if present.
+    pub value: Option<proc_macro2::TokenStream>,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(Display(…))` attributes on an input.
+pub struct InputDisplay {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+    /// The format string if present.
+    fmt: Option<syn::LitStr>,
 }
 
 #[derive(Debug, Default)]
@@ -127,8 +170,18 @@ pub struct InputOrd {
 pub struct FieldClone {
     /// The `bound` attribute if present and the corresponding bounds.
     bounds: Option<Vec<syn::WherePredicate>>,
-    /// The `clone_with` attribute if present and the path to the cloning function.
-    clone_with: Option<syn::Path>,
+    /// The `clone_from` attribute if present, `false` if the field is cloned in `clone_from`.
+    clone_from: Option<bool>,
+    /// The `clone_from_with` attribute if present and the function to use in `clone_from`.
+    clone_from_with: Option<WithFn>,
+    /// The `clone_with` attribute if present and the cloning function.
+    clone_with: Option<WithFn>,
+    /// Whether the values behind `Rc`, `Arc` and `Box` should be cloned too.
+    deep: bool,
+    /// Whether the field is reset to its default value instead of being cloned.
+    default: bool,
+    /// The `value` attribute if present and the value to use instead of cloning the field.
+    value: Option<proc_macro2::TokenStream>,
 }
 
 #[derive(Debug, Default)]
@@ -136,10 +189,22 @@ pub struct FieldClone {
 pub struct FieldDebug {
     /// The `bound` attribute if present and the corresponding bounds.
     bounds: Option<Vec<syn::WherePredicate>>,
-    /// The `format_with` attribute if present and the path to the formatting function.
-    format_with: Option<syn::Path>,
+    /// Whether the fields of the field should be shown in place of the field.
+    flatten: bool,
+    /// The `format` attribute if present and the format string to use.
+    format: Option<syn::LitStr>,
+    /// The `format_with` attribute if present and the formatting function.
+    format_with: Option<WithFn>,
     /// Whether the field is to be ignored from output.
     ignore: bool,
+    /// The `max_items` attribute if present and the number of items to show.
+    max_items: Option<usize>,
+    /// The `redact` attribute if present and the placeholder to show instead of the value.
+    redact: Option<String>,
+    /// The `rename` attribute if present and the name to use in the output.
+    rename: Option<String>,
+    /// The `skip_if` attribute if present and the predicate function.
+    skip_if: Option<WithFn>,
 }
 
 #[derive(Debug, Default)]
@@ -147,8 +212,12 @@ pub struct FieldDebug {
 pub struct FieldDefault {
     /// The `bound` attribute if present and the corresponding bounds.
     bounds: Option<Vec<syn::WherePredicate>>,
+    /// Whether the field is not an argument of `new="args"`.
+    skip: bool,
     /// The default value for the field if present.
     pub value: Option<proc_macro2::TokenStream>,
+    /// The `with` attribute if present and the path to the function returning the default value.
+    with: Option<syn::Path>,
 }
 
 #[derive(Debug, Default)]
@@ -156,8 +225,8 @@ pub struct FieldDefault {
 pub struct FieldHash {
     /// The `bound` attribute if present and the corresponding bounds.
     bounds: Option<Vec<syn::WherePredicate>>,
-    /// The `hash_with` attribute if present and the path to the hashing function.
-    hash_with: Option<syn::Path>,
+    /// The `hash_with` attribute if present and the hashing function.
+    hash_with: Option<WithFn>,
     /// Whether the field is to be ignored when hashing.
     ignore: bool,
 }
@@ -167,8 +236,8 @@ pub struct FieldHash {
 pub struct FieldPartialEq {
     /// The `bound` attribute if present and the corresponding bounds.
     bounds: Option<Vec<syn::WherePredicate>>,
-    /// The `compare_with` attribute if present and the path to the comparison function.
-    compare_with: Option<syn::Path>,
+    /// The `compare_with` attribute if present and the comparison function.
+    compare_with: Option<WithFn>,
     /// Whether the field is to be ignored when comparing.
     ignore: bool,
 }
@@ -178,8 +247,8 @@ pub struct FieldPartialEq {
 pub struct FieldPartialOrd {
     /// The `bound` attribute if present and the corresponding bounds.
     bounds: Option<Vec<syn::WherePredicate>>,
-    /// The `compare_with` attribute if present and the path to the comparison function.
-    compare_with: Option<syn::Path>,
+    /// The `compare_with` attribute if present and the comparison function.
+    compare_with: Option<WithFn>,
     /// Whether the field is to be ignored when comparing.
     ignore: bool,
 }
@@ -189,8 +258,8 @@ pub struct FieldPartialOrd {
 pub struct FieldOrd {
     /// The `bound` attribute if present and the corresponding bounds.
     bounds: Option<Vec<syn::WherePredicate>>,
-    /// The `compare_with` attribute if present and the path to the comparison function.
-    compare_with: Option<syn::Path>,
+    /// The `compare_with` attribute if present and the comparison function.
+    compare_with: Option<WithFn>,
     /// Whether the field is to be ignored when comparing.
     ignore: bool,
 }
@@ -266,12 +335,21 @@ impl Input {
     ) -> Result<Input, ()> {
         let mut input = Input {
             is_packed: attrs.iter().any(has_repr_packed_attr),
+            repr_int: attrs.iter().filter_map(repr_int_attr).next(),
             ..Default::default()
         };
 
         for_all_attr! {
             errors;
             for (name, values) in attrs;
+            "Builder" => {
+                match_attributes! {
+                    errors for "Builder";
+                    let Some(builder) = input.builder;
+                    for value in values;
+                    "bound" => parse_bound(&mut builder.bounds, value, errors),
+                }
+            }
             "Clone" => {
                 match_attributes! {
                     errors for "Clone";
@@ -281,6 +359,9 @@ impl Input {
                     "clone_from" => {
                         clone.clone_from = parse_boolean_meta_item(value, true, "clone_from", errors);
                     }
+                    "via_copy" => {
+                        clone.via_copy = parse_boolean_meta_item(value, true, "via_copy", errors);
+                    }
                 }
             }
             "Copy" => {
@@ -297,6 +378,25 @@ impl Input {
                     let Some(debug) = input.debug;
                     for value in values;
                     "bound" => parse_bound(&mut debug.bounds, value, errors),
+                    "format_with" => {
+                        let path = value.expect("`format_with` needs a value");
+                        debug.format_with = parse_with_fn(path, errors);
+                    }
+                    "discriminant" => {
+                        debug.discriminant = parse_boolean_meta_item(value, true, "discriminant", errors);
+                    }
+                    "flattenable" => {
+                        debug.flattenable = parse_boolean_meta_item(value, true, "flattenable", errors);
+                    }
+                    "name" => {
+                        debug.name = Some(value.expect("`name` needs a value").clone());
+                    }
+                    "non_exhaustive" => {
+                        debug.non_exhaustive = parse_boolean_meta_item(value, true, "non_exhaustive", errors);
+                    }
+                    "qualified" => {
+                        debug.qualified = parse_boolean_meta_item(value, true, "qualified", errors);
+                    }
                     "transparent" => {
                         debug.transparent = parse_boolean_meta_item(value, true, "transparent", errors);
                     }
@@ -308,8 +408,42 @@ impl Input {
                     let Some(default) = input.default;
                     for value in values;
                     "bound" => parse_bound(&mut default.bounds, value, errors),
+                    "const" => {
+                        default.is_const = parse_boolean_meta_item(value, true, "const", errors);
+                    }
                     "new" => {
-                        default.new = parse_boolean_meta_item(value, true, "new", errors);
+                        if value.map_or(false, |value| value.value() == "args") {
+                            default.new = true;
+                            default.new_with_args = true;
+                        } else {
+                            default.new = parse_boolean_meta_item(value, true, "new", errors);
+                        }
+                    }
+                    "value" => {
+                        let value = value.expect("`value` needs a value");
+                        default.value = parse_str_lit(value, errors).ok();
+                    }
+                }
+            }
+            "Display" => {
+                // `Display="…"` is the format string, not an attribute name
+                let (formats, values): (Vec<_>, Vec<_>) = values
+                    .into_iter()
+                    .partition(|&(name, _)| name.is_none());
+
+                match_attributes! {
+                    errors for "Display";
+                    let Some(display) = input.display;
+                    for value in values;
+                    "bound" => parse_bound(&mut display.bounds, value, errors),
+                    "fmt" => {
+                        display.fmt = Some(value.expect("`fmt` needs a value").clone());
+                    }
+                }
+
+                if let Some(display) = input.display.as_mut() {
+                    for (_, format) in formats {
+                        display.fmt = format.cloned();
                     }
                 }
             }
@@ -371,6 +505,12 @@ impl Input {
         Ok(input)
     }
 
+    pub fn builder_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.builder
+            .as_ref()
+            .and_then(|d| d.bounds.as_ref().map(Vec::as_slice))
+    }
+
     pub fn clone_bound(&self) -> Option<&[syn::WherePredicate]> {
         self.clone
             .as_ref()
@@ -381,6 +521,10 @@ impl Input {
         self.clone.as_ref().map_or(false, |d| d.clone_from)
     }
 
+    pub fn clone_via_copy(&self) -> bool {
+        self.clone.as_ref().map_or(false, |d| d.via_copy)
+    }
+
     pub fn copy_bound(&self) -> Option<&[syn::WherePredicate]> {
         self.copy
             .as_ref()
@@ -393,6 +537,30 @@ impl Input {
             .and_then(|d| d.bounds.as_ref().map(Vec::as_slice))
     }
 
+    pub fn debug_discriminant(&self) -> bool {
+        self.debug.as_ref().map_or(false, |d| d.discriminant)
+    }
+
+    pub fn debug_flattenable(&self) -> bool {
+        self.debug.as_ref().map_or(false, |d| d.flattenable)
+    }
+
+    pub fn debug_format_with(&self) -> Option<&WithFn> {
+        self.debug.as_ref().and_then(|d| d.format_with.as_ref())
+    }
+
+    pub fn debug_name(&self) -> Option<&syn::LitStr> {
+        self.debug.as_ref().and_then(|d| d.name.as_ref())
+    }
+
+    pub fn debug_non_exhaustive(&self) -> bool {
+        self.debug.as_ref().map_or(false, |d| d.non_exhaustive)
+    }
+
+    pub fn debug_qualified(&self) -> bool {
+        self.debug.as_ref().map_or(false, |d| d.qualified)
+    }
+
     pub fn debug_transparent(&self) -> bool {
         self.debug.as_ref().map_or(false, |d| d.transparent)
     }
@@ -403,6 +571,16 @@ impl Input {
             .and_then(|d| d.bounds.as_ref().map(Vec::as_slice))
     }
 
+    pub fn display_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.display
+            .as_ref()
+            .and_then(|d| d.bounds.as_ref().map(Vec::as_slice))
+    }
+
+    pub fn display_fmt(&self) -> Option<&syn::LitStr> {
+        self.display.as_ref().and_then(|d| d.fmt.as_ref())
+    }
+
     pub fn eq_bound(&self) -> Option<&[syn::WherePredicate]> {
         self.eq
             .as_ref()
@@ -459,10 +637,45 @@ impl Field {
                     errors for "Clone";
                     for value in values;
                     "bound" => parse_bound(&mut out.clone.bounds, value, errors),
+                    "clone_from" => {
+                        out.clone.clone_from = Some(parse_boolean_meta_item(value, true, "clone_from", errors));
+                    }
+                    "clone_from_with" => {
+                        let path = value.expect("`clone_from_with` needs a value");
+                        out.clone.clone_from_with = parse_with_fn(path, errors);
+                    }
                     "clone_with" => {
                         let path = value.expect("`clone_with` needs a value");
-                        out.clone.clone_with = parse_str_lit(&path, errors).ok();
+                        out.clone.clone_with = parse_with_fn(path, errors);
+                    }
+                    "deep" => {
+                        out.clone.deep = parse_boolean_meta_item(value, true, "deep", errors);
                     }
+                    "default" => {
+                        out.clone.default = parse_boolean_meta_item(value, true, "default", errors);
+                    }
+                    "value" => {
+                        let value = value.expect("`value` needs a value");
+                        out.clone.value = parse_str_lit(value, errors).ok();
+                    }
+                }
+                let replacements = [
+                    out.clone.clone_with.is_some(),
+                    out.clone.deep,
+                    out.clone.default,
+                    out.clone.value.is_some(),
+                ];
+                if replacements.iter().filter(|&&r| r).count() > 1 {
+                    errors.extend(quote_spanned! {name.span()=>
+                        compile_error!("`clone_with`, `deep`, `default` and `value` can't be used together");
+                    });
+                }
+                if out.clone.clone_from_with.is_some()
+                    && (out.clone.clone_from == Some(false) || out.clone.default || out.clone.value.is_some())
+                {
+                    errors.extend(quote_spanned! {name.span()=>
+                        compile_error!("`clone_from_with` can't be used with `clone_from=\"false\"`, `default` or `value`");
+                    });
                 }
             }
             "Debug" => {
@@ -470,13 +683,40 @@ impl Field {
                     errors for "Debug";
                     for value in values;
                     "bound" => parse_bound(&mut out.debug.bounds, value, errors),
+                    "flatten" => {
+                        out.debug.flatten = parse_boolean_meta_item(value, true, "flatten", errors);
+                    }
+                    "format" => {
+                        out.debug.format = Some(value.expect("`format` needs a value").clone());
+                    }
                     "format_with" => {
                         let path = value.expect("`format_with` needs a value");
-                        out.debug.format_with = parse_str_lit(&path, errors).ok();
+                        out.debug.format_with = parse_with_fn(path, errors);
                     }
                     "ignore" => {
                         out.debug.ignore = parse_boolean_meta_item(value, true, "ignore", errors);
                     }
+                    "max_items" => {
+                        let max_items = value.expect("`max_items` needs a value");
+                        out.debug.max_items = parse_usize(max_items, "max_items", errors);
+                    }
+                    "redact" => {
+                        out.debug.redact = parse_redact(value);
+                    }
+                    "rename" => {
+                        let rename = value.expect("`rename` needs a value");
+                        if field.ident.is_some() {
+                            out.debug.rename = Some(rename.value());
+                        } else {
+                            errors.extend(quote_spanned! {rename.span()=>
+                                compile_error!("`rename` can only be used on named fields");
+                            });
+                        }
+                    }
+                    "skip_if" => {
+                        let path = value.expect("`skip_if` needs a value");
+                        out.debug.skip_if = parse_with_fn(path, errors);
+                    }
                 }
             }
             "Default" => {
@@ -484,10 +724,22 @@ impl Field {
                     errors for "Default";
                     for value in values;
                     "bound" => parse_bound(&mut out.default.bounds, value, errors),
+                    "skip" => {
+                        out.default.skip = parse_boolean_meta_item(value, true, "skip", errors);
+                    }
                     "value" => {
                         let value = value.expect("`value` needs a value");
                         out.default.value = parse_str_lit(&value, errors).ok();
                     }
+                    "with" => {
+                        let path = value.expect("`with` needs a value");
+                        out.default.with = parse_str_lit(path, errors).ok();
+                    }
+                }
+                if out.default.value.is_some() && out.default.with.is_some() {
+                    errors.extend(quote_spanned! {name.span()=>
+                        compile_error!("`value` and `with` can't be used together");
+                    });
                 }
             }
             "Eq" => {
@@ -504,7 +756,7 @@ impl Field {
                     "bound" => parse_bound(&mut out.hash.bounds, value, errors),
                     "hash_with" => {
                         let path = value.expect("`hash_with` needs a value");
-                        out.hash.hash_with = parse_str_lit(&path, errors).ok();
+                        out.hash.hash_with = parse_with_fn(path, errors);
                     }
                     "ignore" => {
                         out.hash.ignore = parse_boolean_meta_item(value, true, "ignore", errors);
@@ -518,7 +770,7 @@ impl Field {
                     "bound" => parse_bound(&mut out.partial_eq.bounds, value, errors),
                     "compare_with" => {
                         let path = value.expect("`compare_with` needs a value");
-                        out.partial_eq.compare_with = parse_str_lit(&path, errors).ok();
+                        out.partial_eq.compare_with = parse_with_fn(path, errors);
                     }
                     "ignore" => {
                         out.partial_eq.ignore = parse_boolean_meta_item(value, true, "ignore", errors);
@@ -532,7 +784,7 @@ impl Field {
                     "bound" => parse_bound(&mut out.partial_ord.bounds, value, errors),
                     "compare_with" => {
                         let path = value.expect("`compare_with` needs a value");
-                        out.partial_ord.compare_with = parse_str_lit(&path, errors).ok();
+                        out.partial_ord.compare_with = parse_with_fn(path, errors);
                     }
                     "ignore" => {
                         out.partial_ord.ignore = parse_boolean_meta_item(value, true, "ignore", errors);
@@ -546,7 +798,7 @@ impl Field {
                     "bound" => parse_bound(&mut out.ord.bounds, value, errors),
                     "compare_with" => {
                         let path = value.expect("`compare_with` needs a value");
-                        out.ord.compare_with = parse_str_lit(&path, errors).ok();
+                        out.ord.compare_with = parse_with_fn(path, errors);
                     }
                     "ignore" => {
                         out.ord.ignore = parse_boolean_meta_item(value, true, "ignore", errors);
@@ -561,6 +813,46 @@ impl Field {
             }
         }
 
+        if let (Some(format), Some(_)) = (&out.debug.format, &out.debug.format_with) {
+            errors.extend(quote_spanned! {format.span()=>
+                compile_error!("`format` and `format_with` can't be used together");
+            });
+        }
+        if out.debug.max_items.is_some()
+            && (out.debug.format.is_some() || out.debug.format_with.is_some())
+        {
+            errors.extend(quote_spanned! {field.span()=>
+                compile_error!("`max_items` can't be used with `format` or `format_with`");
+            });
+        }
+        if out.debug.redact.is_some()
+            && (out.debug.format.is_some()
+                || out.debug.format_with.is_some()
+                || out.debug.max_items.is_some())
+        {
+            errors.extend(quote_spanned! {field.span()=>
+                compile_error!("`redact` can't be used with `format`, `format_with` or `max_items`");
+            });
+        }
+        if out.debug.flatten {
+            if field.ident.is_none() {
+                errors.extend(quote_spanned! {field.span()=>
+                    compile_error!("`flatten` can only be used on named fields");
+                });
+                out.debug.flatten = false;
+            } else if out.debug.format.is_some()
+                || out.debug.format_with.is_some()
+                || out.debug.max_items.is_some()
+                || out.debug.redact.is_some()
+                || out.debug.rename.is_some()
+            {
+                errors.extend(quote_spanned! {field.span()=>
+                    compile_error!("`flatten` can't be used with `format`, `format_with`, `max_items`, `redact` or `rename`");
+                });
+                out.debug.flatten = false;
+            }
+        }
+
         Ok(out)
     }
 
@@ -568,7 +860,28 @@ impl Field {
         self.clone.bounds.as_ref().map(Vec::as_slice)
     }
 
-    pub fn clone_with(&self) -> Option<&syn::Path> {
+    /// Whether `clone_from` should call `clone_from` on this field rather than clone it.
+    pub fn clone_from(&self) -> bool {
+        self.clone.clone_from.unwrap_or(true)
+    }
+
+    pub fn clone_from_with(&self) -> Option<&WithFn> {
+        self.clone.clone_from_with.as_ref()
+    }
+
+    pub fn clone_deep(&self) -> bool {
+        self.clone.deep
+    }
+
+    pub fn clone_default(&self) -> bool {
+        self.clone.default
+    }
+
+    pub fn clone_value(&self) -> Option<&proc_macro2::TokenStream> {
+        self.clone.value.as_ref()
+    }
+
+    pub fn clone_with(&self) -> Option<&WithFn> {
         self.clone.clone_with.as_ref()
     }
 
@@ -580,14 +893,38 @@ impl Field {
         self.debug.bounds.as_ref().map(Vec::as_slice)
     }
 
-    pub fn debug_format_with(&self) -> Option<&syn::Path> {
+    pub fn debug_flatten(&self) -> bool {
+        self.debug.flatten
+    }
+
+    pub fn debug_format(&self) -> Option<&syn::LitStr> {
+        self.debug.format.as_ref()
+    }
+
+    pub fn debug_format_with(&self) -> Option<&WithFn> {
         self.debug.format_with.as_ref()
     }
 
+    pub fn debug_skip_if(&self) -> Option<&WithFn> {
+        self.debug.skip_if.as_ref()
+    }
+
     pub fn ignore_debug(&self) -> bool {
         self.debug.ignore
     }
 
+    pub fn debug_max_items(&self) -> Option<usize> {
+        self.debug.max_items
+    }
+
+    pub fn debug_redact(&self) -> Option<&str> {
+        self.debug.redact.as_ref().map(String::as_str)
+    }
+
+    pub fn debug_rename(&self) -> Option<&str> {
+        self.debug.rename.as_ref().map(String::as_str)
+    }
+
     pub fn ignore_hash(&self) -> bool {
         self.hash.ignore
     }
@@ -600,6 +937,15 @@ impl Field {
         self.default.value.as_ref()
     }
 
+    pub fn default_with(&self) -> Option<&syn::Path> {
+        self.default.with.as_ref()
+    }
+
+    /// Whether the field is filled automatically by `new="args"`.
+    pub fn default_skip(&self) -> bool {
+        self.default.skip || self.default.value.is_some() || self.default.with.is_some()
+    }
+
     pub fn eq_bound(&self) -> Option<&[syn::WherePredicate]> {
         self.eq_bound.as_ref().map(Vec::as_slice)
     }
@@ -608,7 +954,7 @@ impl Field {
         self.hash.bounds.as_ref().map(Vec::as_slice)
     }
 
-    pub fn hash_with(&self) -> Option<&syn::Path> {
+    pub fn hash_with(&self) -> Option<&WithFn> {
         self.hash.hash_with.as_ref()
     }
 
@@ -624,15 +970,15 @@ impl Field {
         self.ord.bounds.as_ref().map(Vec::as_slice)
     }
 
-    pub fn partial_eq_compare_with(&self) -> Option<&syn::Path> {
+    pub fn partial_eq_compare_with(&self) -> Option<&WithFn> {
         self.partial_eq.compare_with.as_ref()
     }
 
-    pub fn partial_ord_compare_with(&self) -> Option<&syn::Path> {
+    pub fn partial_ord_compare_with(&self) -> Option<&WithFn> {
         self.partial_ord.compare_with.as_ref()
     }
 
-    pub fn ord_compare_with(&self) -> Option<&syn::Path> {
+    pub fn ord_compare_with(&self) -> Option<&WithFn> {
         self.ord.compare_with.as_ref()
     }
 
@@ -649,6 +995,24 @@ impl Field {
     }
 }
 
+/// Represent the function given to a `*_with` attribute.
+#[derive(Debug)]
+pub enum WithFn {
+    /// A path to a function, eg. `clone_with="path::to::clone"`.
+    Path(syn::Path),
+    /// Any other expression, eg. `compare_with="|a, b| a.eq_ignore_ascii_case(b)"`.
+    Expr(proc_macro2::TokenStream),
+}
+
+impl quote::ToTokens for WithFn {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        match *self {
+            WithFn::Path(ref path) => path.to_tokens(tokens),
+            WithFn::Expr(ref expr) => expr.to_tokens(tokens),
+        }
+    }
+}
+
 /// Represent an attribute.
 ///
 /// We only have a limited set of possible attributes:
@@ -793,6 +1157,41 @@ fn parse_boolean_meta_item(
     }
 }
 
+/// Parse an item value as a positive integer. The `name` parameter is used for error reporting.
+fn parse_usize(
+    item: &syn::LitStr,
+    name: &str,
+    errors: &mut proc_macro2::TokenStream,
+) -> Option<usize> {
+    match item.value().parse() {
+        Ok(value) => Some(value),
+        Err(_) => {
+            let message = format!(
+                "expected a positive integer for `{}`, got `{}`",
+                name,
+                item.value()
+            );
+            errors.extend(quote_spanned! {item.span()=>
+                compile_error!(#message);
+            });
+
+            None
+        }
+    }
+}
+
+/// Parse a `redact` item. The value is the placeholder to show instead of the field, `"true"` and
+/// `"redact"` (as in `Debug="redact"`) use a default placeholder and `"false"` disables redaction.
+fn parse_redact(item: Option<&syn::LitStr>) -> Option<String> {
+    let item = item.map(syn::LitStr::value);
+
+    match item.as_ref().map(String::as_str) {
+        None | Some("true") | Some("redact") => Some("***".into()),
+        Some("false") => None,
+        Some(_) => item,
+    }
+}
+
 /// Parse a `bound` item.
 fn parse_bound(
     opt_bounds: &mut Option<Vec<syn::WherePredicate>>,
@@ -839,6 +1238,24 @@ where
     }
 }
 
+/// Parse the value of a `*_with` item, which is either a path or an expression.
+fn parse_with_fn(value: &syn::LitStr, errors: &mut proc_macro2::TokenStream) -> Option<WithFn> {
+    if let Ok(path) = value.parse() {
+        return Some(WithFn::Path(path));
+    }
+
+    match parse_str_lit::<proc_macro2::TokenStream>(value, errors) {
+        Ok(ref expr) if expr.is_empty() => {
+            errors.extend(quote_spanned! {value.span()=>
+                compile_error!("expected a path or an expression");
+            });
+            None
+        }
+        Ok(expr) => Some(WithFn::Expr(expr)),
+        Err(()) => None,
+    }
+}
+
 fn ensure_str_lit<'a>(
     attr_path: &'a syn::Path,
     lit: &'a syn::Lit,
@@ -885,3 +1302,26 @@ pub fn has_repr_packed_attr(attr: &syn::Attribute) -> bool {
 
     false
 }
+
+/// Return the integer type given with `#[repr(…)]`, if any.
+pub fn repr_int_attr(attr: &syn::Attribute) -> Option<syn::Ident> {
+    const INTS: &[&str] = &[
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+    ];
+
+    if let Ok(syn::Meta::List(attr)) = attr.parse_meta() {
+        if attr.path.get_ident().map(|i| i == "repr") == Some(true) {
+            for item in attr.nested {
+                if let syn::NestedMeta::Meta(syn::Meta::Path(item)) = item {
+                    if let Some(ident) = item.get_ident() {
+                        if INTS.iter().any(|int| ident == int) {
+                            return Some(ident.clone());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
@@ -0,0 +1,124 @@
+use proc_macro2;
+
+use ast;
+use default;
+use paths;
+use syn;
+use utils;
+
+/// Derive a builder for `input`, ie. a `<Name>Builder` type with a setter for each field and a
+/// `build` method, and a `builder` method on `input`.
+pub fn derive(
+    input: &ast::Input,
+    errors: &mut proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let fields = match input.body {
+        ast::Body::Struct(ast::Style::Struct, ref fields) => fields,
+        _ => {
+            errors.extend(quote_spanned! {input.ident.span()=>
+                compile_error!("`Builder` can only be derived on structures with named fields");
+            });
+            return proc_macro2::TokenStream::new();
+        }
+    };
+
+    // the setters are named after the fields
+    if let Some(field_name) = fields
+        .iter()
+        .filter_map(|f| f.ident.as_ref())
+        .find(|field_name| *field_name == "build")
+    {
+        errors.extend(quote_spanned! {field_name.span()=>
+            compile_error!("`Builder` can't be derived for a field named `build`, its setter would conflict with the `build` method");
+        });
+        return proc_macro2::TokenStream::new();
+    }
+
+    let name = &input.ident;
+    let vis = input.vis;
+    let builder_name = syn::Ident::new(&format!("{}Builder", name), name.span());
+    let option_path = paths::option_path();
+    let result_path = paths::result_path();
+
+    let mut builder_fields = Vec::new();
+    let mut initializers = Vec::new();
+    let mut setters = Vec::new();
+    let mut values = Vec::new();
+    for f in fields {
+        let field_name = f
+            .ident
+            .as_ref()
+            .expect("A structure field must have a name");
+        let ty = f.ty;
+
+        builder_fields.push(quote!(#field_name: #option_path<#ty>));
+        initializers.push(quote!(#field_name: #option_path::None));
+        let setter_doc = format!("Sets the field `{}`.", field_name);
+        setters.push(quote! {
+            #[doc = #setter_doc]
+            pub fn #field_name(mut self, #field_name: #ty) -> Self {
+                self.#field_name = #option_path::Some(#field_name);
+                self
+            }
+        });
+
+        // fields with a default value are optional
+        let missing = if f.attrs.default_skip() {
+            default::field_default(f)
+        } else {
+            let message = format!("missing field `{}`", field_name);
+            quote!(return #result_path::Err(#message))
+        };
+        values.push(quote! {
+            #field_name: match self.#field_name {
+                #option_path::Some(__value) => __value,
+                #option_path::None => #missing,
+            }
+        });
+    }
+
+    // only the fields that are filled with `Default::default()` need to implement `Default`
+    let generics = utils::build_impl_generics(
+        input,
+        &paths::default_trait_path(),
+        |attrs| attrs.default_skip() && !default::has_default_value(attrs),
+        |_| None,
+        |input| input.builder_bound(),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let type_generics = input.generics;
+    let type_where_clause = &input.generics.where_clause;
+
+    let builder_doc = format!("A builder for `{}`.", name);
+    let builder_fn_doc = format!("Creates a builder for `{}`.", name);
+
+    quote! {
+        #[doc = #builder_doc]
+        #vis struct #builder_name #type_generics #type_where_clause {
+            #(#builder_fields),*
+        }
+
+        #[allow(unused_qualifications)]
+        impl #impl_generics #name #ty_generics #where_clause {
+            #[doc = #builder_fn_doc]
+            #[inline]
+            pub fn builder() -> #builder_name #ty_generics {
+                #builder_name {
+                    #(#initializers),*
+                }
+            }
+        }
+
+        #[allow(unused_qualifications)]
+        impl #impl_generics #builder_name #ty_generics #where_clause {
+            #(#setters)*
+
+            /// Builds the value, or returns an error naming a missing field.
+            pub fn build(self) -> #result_path<#name #ty_generics, &'static str> {
+                #result_path::Ok(#name {
+                    #(#values),*
+                })
+            }
+        }
+    }
+}
@@ -2,7 +2,9 @@ use proc_macro2;
 
 use ast;
 use attr;
+use bound;
 use matcher;
+use paths;
 use syn;
 use utils;
 
@@ -27,10 +29,93 @@ pub fn derive_copy(input: &ast::Input) -> proc_macro2::TokenStream {
 }
 
 /// Derive `Clone` for `input`.
-pub fn derive_clone(input: &ast::Input) -> proc_macro2::TokenStream {
+pub fn derive_clone(
+    input: &ast::Input,
+    errors: &mut proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
     let name = &input.ident;
 
     let clone_trait_path = clone_trait_path();
+
+    let mut has_deep_error = false;
+    for field in input.body.all_fields() {
+        if !field.attrs.clone_deep() {
+            continue;
+        }
+        if !is_deep_wrapper(field.ty) {
+            errors.extend(quote_spanned! {field.span=>
+                compile_error!("`Clone=\"deep\"` can only be used on fields of type `Rc`, `Arc`, `Box`, `Option` or `Vec`");
+            });
+            has_deep_error = true;
+        } else if has_deep_trait_object(field.ty) {
+            errors.extend(quote_spanned! {field.span=>
+                compile_error!("`Clone=\"deep\"` can't clone trait objects behind `Rc`, `Arc` or `Box`");
+            });
+            has_deep_error = true;
+        }
+    }
+    if has_deep_error {
+        return proc_macro2::TokenStream::new();
+    }
+
+    // `clone_from` is only implemented if asked for on the type
+    if !input.attrs.clone_from() {
+        for field in input.body.all_fields() {
+            if !field.attrs.clone_from() || field.attrs.clone_from_with().is_some() {
+                errors.extend(quote_spanned! {field.span=>
+                    compile_error!("`clone_from` and `clone_from_with` can only be used on fields of types with `Clone(clone_from=\"true\")`");
+                });
+            }
+        }
+    }
+
+    // Unions can only be cloned by copying them, like `derive(Clone)` does, other types can ask for
+    // it with `via_copy`
+    let is_union = if let ast::Body::Union(_) = input.body {
+        true
+    } else {
+        false
+    };
+    if is_union || input.attrs.clone_via_copy() {
+        for field in input.body.all_fields() {
+            let attrs = &field.attrs;
+            if attrs.clone_with().is_some() || attrs.clone_deep() || is_replaced(attrs) {
+                errors.extend(quote_spanned! {field.span=>
+                    compile_error!("fields of types cloned by copy can't use `clone_with`, `deep`, `default` or `value`");
+                });
+            }
+        }
+
+        let generics = utils::build_impl_generics(
+            input,
+            &copy_trait_path(),
+            |attrs| attrs.clone_bound().is_none(),
+            |field| field.clone_bound(),
+            |input| input.clone_bound(),
+        );
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        return quote! {
+            #[allow(unused_qualifications)]
+            impl #impl_generics #clone_trait_path for #name #ty_generics #where_clause {
+                fn clone(&self) -> Self {
+                    *self
+                }
+            }
+        };
+    }
+
+    // a clone must be the same as a copy, which can't reset fields
+    if input.attrs.copy.is_some() {
+        for field in input.body.all_fields() {
+            if is_replaced(&field.attrs) {
+                errors.extend(quote_spanned! {field.span=>
+                    compile_error!("fields of `Copy` types can't use `Clone=\"default\"` or `Clone(value)`");
+                });
+            }
+        }
+    }
+
     let generics = utils::build_impl_generics(
         input,
         &clone_trait_path,
@@ -38,6 +123,17 @@ pub fn derive_clone(input: &ast::Input) -> proc_macro2::TokenStream {
         |field| field.clone_bound(),
         |input| input.clone_bound(),
     );
+    // fields reset with `Clone(default)` need `Default` instead of `Clone`
+    let generics = if input.attrs.clone_bound().is_none() {
+        bound::with_bound(
+            input,
+            &generics,
+            |attrs| attrs.clone_default() && attrs.clone_bound().is_none(),
+            &paths::default_trait_path(),
+        )
+    } else {
+        generics
+    };
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     let is_copy = input.attrs.copy.is_some();
@@ -51,18 +147,11 @@ pub fn derive_clone(input: &ast::Input) -> proc_macro2::TokenStream {
             }
         }
     } else {
-        let body = matcher::Matcher::new(matcher::BindingStyle::Ref, input.attrs.is_packed).build_arms(
-            input,
-            "__arg",
-            |arm_path, _, _, style, _, bis| {
+        let body = matcher::Matcher::new(matcher::BindingStyle::Ref, input.attrs.is_packed)
+            .with_field_filter(|f: &ast::Field| !is_replaced(&f.attrs))
+            .build_arms(input, "__arg", |arm_path, _, _, style, _, bis| {
                 let field_clones = bis.iter().map(|bi| {
-                    let arg = &bi.expr;
-
-                    let clone = if let Some(clone_with) = bi.field.attrs.clone_with() {
-                        quote!(#clone_with(&#arg))
-                    } else {
-                        quote!(#arg.clone())
-                    };
+                    let clone = field_clone(bi.field, &bi.expr);
 
                     if let Some(ref name) = bi.field.ident {
                         quote! {
@@ -92,8 +181,7 @@ pub fn derive_clone(input: &ast::Input) -> proc_macro2::TokenStream {
                         }
                     }
                 }
-            },
-        );
+            });
 
         let clone_from = if input.attrs.clone_from() {
             Some(
@@ -111,7 +199,26 @@ pub fn derive_clone(input: &ast::Input) -> proc_macro2::TokenStream {
                                             let outer = &outer_bi.expr;
                                             let inner = &inner_bi.expr;
 
-                                            quote!(#outer.clone_from(&#inner);)
+                                            let attrs = &outer_bi.field.attrs;
+
+                                            if let Some(clone_from_with) = attrs.clone_from_with() {
+                                                let ty = outer_bi.field.ty;
+                                                let clone_from = utils::call_with(
+                                                    clone_from_with,
+                                                    quote!(Fn(&mut #ty, &#ty)),
+                                                    quote!(&mut #outer, &#inner),
+                                                );
+                                                quote!(#clone_from;)
+                                            } else if attrs.clone_from()
+                                                && attrs.clone_with().is_none()
+                                                && !attrs.clone_deep()
+                                                && !is_replaced(attrs)
+                                            {
+                                                quote!(#outer.clone_from(&#inner);)
+                                            } else {
+                                                let clone = field_clone(outer_bi.field, inner);
+                                                quote!(#outer = #clone;)
+                                            }
                                         },
                                     );
 
@@ -174,7 +281,130 @@ pub fn derive_clone(input: &ast::Input) -> proc_macro2::TokenStream {
 }
 
 fn needs_clone_bound(attrs: &attr::Field) -> bool {
-    attrs.clone_bound().is_none()
+    attrs.clone_bound().is_none() && !is_replaced(attrs)
+}
+
+/// Whether a field gets a new value instead of being cloned.
+fn is_replaced(attrs: &attr::Field) -> bool {
+    attrs.clone_default() || attrs.clone_value().is_some()
+}
+
+/// Clone the field whose value is `arg`.
+fn field_clone(field: &ast::Field, arg: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    if let Some(replacement) = replacement(&field.attrs) {
+        replacement
+    } else if let Some(clone_with) = field.attrs.clone_with() {
+        let ty = field.ty;
+        utils::call_with(clone_with, quote!(Fn(&#ty) -> #ty), quote!(&#arg))
+    } else if field.attrs.clone_deep() {
+        deep_clone(field.ty, quote!(&#arg))
+    } else {
+        quote!(#arg.clone())
+    }
+}
+
+/// Clone `value`, a reference to a `ty`, including the values behind `Rc`, `Arc` and `Box`.
+/// `Option` and `Vec` are cloned item by item.
+fn deep_clone(ty: &syn::Type, value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    if let Some((name, inner_ty)) = wrapper_type(ty) {
+        match name.as_ref() {
+            // `str` and slices are unsized, the new pointer is built from a reference to them
+            "Arc" | "Box" | "Rc" if is_str_or_slice(inner_ty) => {
+                return quote!(<#ty>::from(&**#value));
+            }
+            "Arc" | "Box" | "Rc" if !is_trait_object(inner_ty) => {
+                let inner = deep_clone(inner_ty, quote!(&**#value));
+                return quote!(<#ty>::new(#inner));
+            }
+            "Option" => {
+                let inner = deep_clone(inner_ty, quote!(__v));
+                return quote!((#value).as_ref().map(|__v| #inner));
+            }
+            "Vec" => {
+                let inner = deep_clone(inner_ty, quote!(__v));
+                return quote!((#value).iter().map(|__v| #inner).collect::<#ty>());
+            }
+            _ => (),
+        }
+    }
+
+    let clone_trait_path = clone_trait_path();
+    quote!(#clone_trait_path::clone(#value))
+}
+
+/// Whether `ty` contains a trait object behind a pointer that `deep_clone` would clone.
+fn has_deep_trait_object(ty: &syn::Type) -> bool {
+    match wrapper_type(ty) {
+        Some((ref name, inner_ty)) if name == "Arc" || name == "Box" || name == "Rc" => {
+            is_trait_object(inner_ty) || has_deep_trait_object(inner_ty)
+        }
+        Some((ref name, inner_ty)) if name == "Option" || name == "Vec" => {
+            has_deep_trait_object(inner_ty)
+        }
+        _ => false,
+    }
+}
+
+fn is_deep_wrapper(ty: &syn::Type) -> bool {
+    match wrapper_type(ty) {
+        Some((ref name, _)) => ["Arc", "Box", "Option", "Rc", "Vec"].contains(&name.as_ref()),
+        None => false,
+    }
+}
+
+/// The name and type parameter of a type with exactly one type parameter, like `Rc<T>`.
+fn wrapper_type(ty: &syn::Type) -> Option<(String, &syn::Type)> {
+    if let syn::Type::Path(syn::TypePath {
+        qself: None,
+        ref path,
+    }) = *ty
+    {
+        let segment = path
+            .segments
+            .last()
+            .expect("a path has at least one segment");
+
+        if let syn::PathArguments::AngleBracketed(ref arguments) = segment.arguments {
+            if arguments.args.len() == 1 {
+                if let syn::GenericArgument::Type(ref inner_ty) = arguments.args[0] {
+                    return Some((segment.ident.to_string(), inner_ty));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+fn is_str_or_slice(ty: &syn::Type) -> bool {
+    match *ty {
+        syn::Type::Slice(_) => true,
+        syn::Type::Path(syn::TypePath {
+            qself: None,
+            ref path,
+        }) => path.is_ident("str"),
+        _ => false,
+    }
+}
+
+fn is_trait_object(ty: &syn::Type) -> bool {
+    match *ty {
+        syn::Type::TraitObject(_) => true,
+        syn::Type::Paren(ref paren) => is_trait_object(&paren.elem),
+        _ => false,
+    }
+}
+
+/// The new value of a field that is not cloned, if any.
+fn replacement(attrs: &attr::Field) -> Option<proc_macro2::TokenStream> {
+    if let Some(value) = attrs.clone_value() {
+        Some(quote!(#value))
+    } else if attrs.clone_default() {
+        let default_trait_path = paths::default_trait_path();
+        Some(quote!(#default_trait_path::default()))
+    } else {
+        None
+    }
 }
 
 /// Return the path of the `Clone` trait, that is `::std::clone::Clone`.
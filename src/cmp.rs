@@ -76,7 +76,13 @@ pub fn derive_partial_eq(input: &ast::Input) -> proc_macro2::TokenStream {
                         if o.field.attrs.ignore_partial_eq() {
                             None
                         } else if let Some(compare_fn) = o.field.attrs.partial_eq_compare_with() {
-                            Some(quote!(&& #compare_fn(&#outer_name, &#inner_name)))
+                            let ty = o.field.ty;
+                            let compare = utils::call_with(
+                                compare_fn,
+                                quote!(Fn(&#ty, &#ty) -> bool),
+                                quote!(&#outer_name, &#inner_name),
+                            );
+                            Some(quote!(&& #compare))
                         } else {
                             Some(quote!(&& &#outer_name == &#inner_name))
                         }
@@ -118,7 +124,7 @@ pub fn derive_partial_ord(
         }
     }
 
-    let option_path = option_path();
+    let option_path = paths::option_path();
     let ordering_path = ordering_path();
 
     let body = matcher::Matcher::new(matcher::BindingStyle::Ref, input.attrs.is_packed)
@@ -147,17 +153,23 @@ pub fn derive_partial_ord(
                                     if o.field.attrs.ignore_partial_ord() {
                                         acc
                                     } else {
-                                        let cmp_fn = o
-                                            .field
-                                            .attrs
-                                            .partial_ord_compare_with()
-                                            .map(|f| quote!(#f))
-                                            .unwrap_or_else(|| {
+                                        let args = quote!(&#outer_name, &#inner_name);
+                                        let cmp = match o.field.attrs.partial_ord_compare_with() {
+                                            Some(cmp_fn) => {
+                                                let ty = o.field.ty;
+                                                utils::call_with(
+                                                    cmp_fn,
+                                                    quote!(Fn(&#ty, &#ty) -> #option_path<#ordering_path>),
+                                                    args,
+                                                )
+                                            }
+                                            None => {
                                                 let path = partial_ord_trait_path();
-                                                quote!(#path::partial_cmp)
-                                            });
+                                                quote!(#path::partial_cmp(#args))
+                                            }
+                                        };
 
-                                        quote!(match #cmp_fn(&#outer_name, &#inner_name) {
+                                        quote!(match #cmp {
                                             #option_path::Some(#equal_path) => #acc,
                                             __derive_ordering_other => __derive_ordering_other,
                                         })
@@ -245,17 +257,23 @@ pub fn derive_ord(
                                     if o.field.attrs.ignore_ord() {
                                         acc
                                     } else {
-                                        let cmp_fn = o
-                                            .field
-                                            .attrs
-                                            .ord_compare_with()
-                                            .map(|f| quote!(#f))
-                                            .unwrap_or_else(|| {
+                                        let args = quote!(&#outer_name, &#inner_name);
+                                        let cmp = match o.field.attrs.ord_compare_with() {
+                                            Some(cmp_fn) => {
+                                                let ty = o.field.ty;
+                                                utils::call_with(
+                                                    cmp_fn,
+                                                    quote!(Fn(&#ty, &#ty) -> #ordering_path),
+                                                    args,
+                                                )
+                                            }
+                                            None => {
                                                 let path = ord_trait_path();
-                                                quote!(#path::cmp)
-                                            });
+                                                quote!(#path::cmp(#args))
+                                            }
+                                        };
 
-                                        quote!(match #cmp_fn(&#outer_name, &#inner_name) {
+                                        quote!(match #cmp {
                                            #equal_path => #acc,
                                             __derive_ordering_other => __derive_ordering_other,
                                         })
@@ -357,15 +375,6 @@ fn ord_trait_path() -> syn::Path {
     }
 }
 
-/// Return the path of the `Option` trait, that is `::std::option::Option`.
-fn option_path() -> syn::Path {
-    if cfg!(feature = "use_core") {
-        parse_quote!(::core::option::Option)
-    } else {
-        parse_quote!(::std::option::Option)
-    }
-}
-
 /// Return the path of the `Ordering` trait, that is `::std::cmp::Ordering`.
 fn ordering_path() -> syn::Path {
     if cfg!(feature = "use_core") {
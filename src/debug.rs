@@ -3,55 +3,211 @@ use proc_macro2;
 use ast;
 use attr;
 use matcher;
+use paths;
 use syn;
 use syn::spanned::Spanned;
 use utils;
 
-pub fn derive(input: &ast::Input) -> proc_macro2::TokenStream {
+pub fn derive(
+    input: &ast::Input,
+    errors: &mut proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
     let debug_trait_path = debug_trait_path();
-    let fmt_path = fmt_path();
+    let fmt_path = paths::fmt_path();
+
+    // The name of the enumeration, if variant names should be qualified with it
+    let qualifier = match input.body {
+        ast::Body::Enum(_) if input.attrs.debug_qualified() => Some(
+            input
+                .attrs
+                .debug_name()
+                .map_or_else(|| input.ident.to_string(), syn::LitStr::value),
+        ),
+        ast::Body::Enum(_) => {
+            if let Some(name) = input.attrs.debug_name() {
+                errors.extend(quote_spanned! {name.span()=>
+                    compile_error!("`Debug(name)` can only be used on an enumeration with `Debug=\"qualified\"`, use it on its variants instead");
+                });
+            }
+            None
+        }
+        ast::Body::Struct(..) | ast::Body::Union(_) => None,
+    };
+    // only structures with named fields that show them can be flattened
+    let can_flatten = match input.body {
+        ast::Body::Struct(ast::Style::Struct, _) => {
+            !input.attrs.debug_transparent() && input.attrs.debug_format_with().is_none()
+        }
+        _ => false,
+    };
+    let mut check_enum_attrs = |name: &syn::Ident, attrs: &attr::Input, is_enum: bool| {
+        if attrs.debug_qualified() && !is_enum {
+            errors.extend(quote_spanned! {name.span()=>
+                compile_error!("`Debug=\"qualified\"` can only be used on enumerations");
+            });
+        }
+        if attrs.debug_discriminant() && !(is_enum && input.is_trivial_enum()) {
+            errors.extend(quote_spanned! {name.span()=>
+                compile_error!("`Debug=\"discriminant\"` can only be used on enumerations without fields");
+            });
+        }
+        if attrs.debug_flattenable() && (is_enum || !can_flatten) {
+            errors.extend(quote_spanned! {name.span()=>
+                compile_error!("`Debug=\"flattenable\"` can only be used on structures with named fields, without `transparent` or `format_with`");
+            });
+        }
+    };
+    match input.body {
+        ast::Body::Enum(ref variants) => {
+            check_enum_attrs(&input.ident, &input.attrs, true);
+            for variant in variants {
+                check_enum_attrs(&variant.ident, &variant.attrs, false);
+            }
+        }
+        ast::Body::Struct(..) | ast::Body::Union(_) => {
+            check_enum_attrs(&input.ident, &input.attrs, false)
+        }
+    }
+
+    let mut has_transparent_error = false;
+    let mut check_transparent = |name: &syn::Ident, attrs: &attr::Input, fields: &[ast::Field]| {
+        if !is_transparent(input, attrs) {
+            return;
+        }
+        let shown_fields = fields.iter().filter(|f| !f.attrs.ignore_debug()).count();
+        if shown_fields != 1 {
+            errors.extend(quote_spanned! {name.span()=>
+                compile_error!("`Debug=\"transparent\"` can only be used on types and variants with exactly one shown field");
+            });
+            has_transparent_error = true;
+        }
+        // the shown field is always printed
+        for field in fields.iter().filter(|f| f.attrs.debug_skip_if().is_some()) {
+            errors.extend(quote_spanned! {field.span=>
+                compile_error!("`skip_if` can't be used on fields of `Debug=\"transparent\"` types and variants");
+            });
+            has_transparent_error = true;
+        }
+    };
+    match input.body {
+        ast::Body::Enum(ref variants) => {
+            for variant in variants {
+                check_transparent(&variant.ident, &variant.attrs, &variant.fields);
+            }
+        }
+        ast::Body::Struct(_, ref fields) => check_transparent(&input.ident, &input.attrs, fields),
+        // none of the fields of a union can be shown
+        ast::Body::Union(_) => check_transparent(&input.ident, &input.attrs, &[]),
+    }
+    if has_transparent_error {
+        return proc_macro2::TokenStream::new();
+    }
 
     let formatter = quote_spanned! {input.span=> __f};
 
-    let body = matcher::Matcher::new(matcher::BindingStyle::Ref, input.attrs.is_packed)
-        .with_field_filter(|f: &ast::Field| !f.attrs.ignore_debug())
-        .build_arms(input, "__arg", |_, _, arm_name, style, attrs, bis| {
-            let field_prints = bis.iter().filter_map(|bi| {
-                if bi.field.attrs.ignore_debug() {
-                    return None;
+    if let Some(body) = whole_type_body(input, &formatter) {
+        let name = &input.ident;
+        let generics = utils::build_impl_generics(
+            input,
+            &debug_trait_path,
+            |_| false,
+            |field| field.debug_bound(),
+            |input| input.debug_bound(),
+        );
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        return quote_spanned! {input.span=>
+            #[allow(unused_qualifications)]
+            impl #impl_generics #debug_trait_path for #name #ty_generics #where_clause {
+                fn fmt(&self, #formatter: &mut #fmt_path::Formatter) -> #fmt_path::Result {
+                    #body
                 }
+            }
+        };
+    }
 
-                if attrs.debug_transparent() {
-                    return Some(quote_spanned! {arm_name.span()=>
-                        #debug_trait_path::fmt(__arg_0, #formatter)
-                    });
+    // `in_debug_fields` is whether the fields are shown by `__derivative_debug_fields`, where the
+    // builder is already a reference
+    let field_prints = |arm_name: &syn::Ident,
+                        attrs: &attr::Input,
+                        bis: &[matcher::BindingInfo],
+                        in_debug_fields: bool| {
+        bis.iter()
+            .filter_map(|bi| {
+                if bi.field.attrs.ignore_debug() {
+                    return None;
                 }
 
                 let arg_expr = &bi.expr;
                 let arg_ident = &bi.ident;
 
-                let dummy_debug = bi.field.attrs.debug_format_with().map(|format_fn| {
-                    format_with(
-                        bi.field,
-                        &input.attrs.debug_bound(),
-                        &arg_expr,
-                        &arg_ident,
-                        format_fn,
-                        input.generics.clone(),
-                    )
-                });
-                let expr = if bi.field.attrs.debug_format_with().is_some() {
+                let dummy_debug = bi
+                    .field
+                    .attrs
+                    .debug_format_with()
+                    .filter(|_| bi.field.attrs.debug_redact().is_none())
+                    .map(|format_fn| {
+                        format_with(
+                            bi.field,
+                            &input.attrs.debug_bound(),
+                            &arg_expr,
+                            &arg_ident,
+                            format_fn,
+                            input.generics.clone(),
+                        )
+                    });
+                let expr = if let Some(placeholder) = bi.field.attrs.debug_redact() {
+                    quote_spanned! {arm_name.span()=>
+                        &format_args!("{}", #placeholder)
+                    }
+                } else if let Some(format) = bi.field.attrs.debug_format() {
+                    quote_spanned! {format.span()=>
+                        &format_args!(#format, #arg_expr)
+                    }
+                } else if bi.field.attrs.debug_format_with().is_some() {
                     quote_spanned! {arm_name.span()=>
                         &#arg_ident
                     }
+                } else if let Some(max_items) = bi.field.attrs.debug_max_items() {
+                    // `&T` is iterable for most collections, but `&&[T]` is not
+                    let collection = if let syn::Type::Reference(_) = *bi.field.ty {
+                        quote!(#arg_expr)
+                    } else {
+                        quote!(&#arg_expr)
+                    };
+                    quote_spanned! {arm_name.span()=>
+                        &__DerivativeTruncated(#collection, #max_items)
+                    }
                 } else {
                     quote_spanned! {arm_name.span()=>
                         &&#arg_expr
                     }
                 };
 
-                let builder = if let Some(ref name) = bi.field.ident {
-                    let name = name.to_string();
+                if is_transparent(input, attrs) {
+                    return Some(quote_spanned! {arm_name.span()=>
+                        #dummy_debug
+                        #debug_trait_path::fmt(#expr, #formatter)
+                    });
+                }
+
+                let builder = if bi.field.attrs.debug_flatten() {
+                    let builder = if in_debug_fields {
+                        quote_spanned! {arm_name.span()=> __debug_trait_builder}
+                    } else {
+                        quote_spanned! {arm_name.span()=> &mut __debug_trait_builder}
+                    };
+                    // point to the field if its type is not `Debug="flattenable"`
+                    let method = quote_spanned! {bi.field.span=> __derivative_debug_fields};
+                    quote_spanned! {arm_name.span()=>
+                        #arg_expr.#method(#builder);
+                    }
+                } else if let Some(ref name) = bi.field.ident {
+                    let name = bi
+                        .field
+                        .attrs
+                        .debug_rename()
+                        .map_or_else(|| name.to_string(), String::from);
                     quote_spanned! {arm_name.span()=>
                         #dummy_debug
                         let _ = __debug_trait_builder.field(#name, #expr);
@@ -63,28 +219,92 @@ pub fn derive(input: &ast::Input) -> proc_macro2::TokenStream {
                     }
                 };
 
-                Some(builder)
-            });
+                if let Some(skip_if) = bi.field.attrs.debug_skip_if() {
+                    let ty = bi.field.ty;
+                    let skip =
+                        utils::call_with(skip_if, quote!(Fn(&#ty) -> bool), quote!(&#arg_expr));
+                    Some(quote_spanned! {skip_if.span()=>
+                        if !#skip {
+                            #builder
+                        }
+                    })
+                } else {
+                    Some(builder)
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+    // redacted fields are only used by their `skip_if` predicate
+    let field_filter = |f: &ast::Field| {
+        !f.attrs.ignore_debug()
+            && (f.attrs.debug_redact().is_none() || f.attrs.debug_skip_if().is_some())
+    };
 
-            let method = match style {
-                ast::Style::Struct => "debug_struct",
-                ast::Style::Tuple | ast::Style::Unit => "debug_tuple",
-            };
-            let method = syn::Ident::new(method, proc_macro2::Span::call_site());
+    let body = matcher::Matcher::new(matcher::BindingStyle::Ref, input.attrs.is_packed)
+        .with_field_filter(field_filter)
+        .build_arms(
+            input,
+            "__arg",
+            |arm_path, _, arm_name, style, attrs, bis| {
+                let field_prints = field_prints(arm_name, attrs, &bis, false);
 
-            if attrs.debug_transparent() {
-                quote_spanned! {arm_name.span()=>
-                    #(#field_prints)*
-                }
-            } else {
-                let name = arm_name.to_string();
-                quote_spanned! {arm_name.span()=>
-                    let mut __debug_trait_builder = #formatter.#method(#name);
-                    #(#field_prints)*
-                    __debug_trait_builder.finish()
+                let method = match style {
+                    ast::Style::Struct => "debug_struct",
+                    ast::Style::Tuple | ast::Style::Unit => "debug_tuple",
+                };
+                let method = syn::Ident::new(method, proc_macro2::Span::call_site());
+
+                // `finish_non_exhaustive` is only available on recent compilers, see `build.rs`
+                let is_non_exhaustive = (attrs.debug_non_exhaustive()
+                    || input.attrs.debug_non_exhaustive())
+                    && bis.iter().any(|bi| bi.field.attrs.ignore_debug());
+                let finish = match style {
+                    ast::Style::Struct if cfg!(derivative_debug_struct_non_exhaustive) => {
+                        is_non_exhaustive
+                    }
+                    ast::Style::Tuple if cfg!(derivative_debug_tuple_non_exhaustive) => {
+                        is_non_exhaustive
+                    }
+                    _ => false,
+                };
+                let finish = if finish {
+                    "finish_non_exhaustive"
+                } else {
+                    "finish"
+                };
+                let finish = syn::Ident::new(finish, proc_macro2::Span::call_site());
+
+                if is_transparent(input, attrs) {
+                    quote_spanned! {arm_name.span()=>
+                        #(#field_prints)*
+                    }
+                } else {
+                    let name = attrs
+                        .debug_name()
+                        .map_or_else(|| arm_name.to_string(), syn::LitStr::value);
+                    let name = match qualifier {
+                        Some(ref qualifier) => format!("{}::{}", qualifier, name),
+                        None => name,
+                    };
+
+                    if input.attrs.debug_discriminant() && input.is_trivial_enum() {
+                        let repr = input.attrs.repr_int.as_ref().map_or_else(
+                            || syn::Ident::new("isize", proc_macro2::Span::call_site()),
+                            Clone::clone,
+                        );
+                        return quote_spanned! {arm_name.span()=>
+                            #formatter.write_fmt(format_args!("{} = {}", #name, #arm_path as #repr))
+                        };
+                    }
+
+                    quote_spanned! {arm_name.span()=>
+                        let mut __debug_trait_builder = #formatter.#method(#name);
+                        #(#field_prints)*
+                        __debug_trait_builder.#finish()
+                    }
                 }
-            }
-        });
+            },
+        );
 
     let name = &input.ident;
 
@@ -97,23 +317,108 @@ pub fn derive(input: &ast::Input) -> proc_macro2::TokenStream {
     );
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
+    let truncated_wrapper = if input
+        .body
+        .all_fields()
+        .iter()
+        .any(|f| f.attrs.debug_max_items().is_some())
+    {
+        Some(truncated_wrapper())
+    } else {
+        None
+    };
+
     // don't attach a span to prevent issue #58
     let match_self = quote!(match *self);
+
+    // Flattenable structures can be flattened into another structure, which calls this method to
+    // show their fields
+    let debug_fields = match input.body {
+        ast::Body::Struct(ast::Style::Struct, _)
+            if can_flatten && input.attrs.debug_flattenable() =>
+        {
+            let fields_body =
+                matcher::Matcher::new(matcher::BindingStyle::Ref, input.attrs.is_packed)
+                    .with_field_filter(field_filter)
+                    .build_arms(input, "__arg", |_, _, arm_name, _, attrs, bis| {
+                        let field_prints = field_prints(arm_name, attrs, &bis, true);
+
+                        quote_spanned! {arm_name.span()=>
+                            #(#field_prints)*
+                        }
+                    });
+
+            // the fields are shown with the span of the structure's name, see `field_prints`
+            let vis = input.vis;
+            let builder = quote_spanned! {input.ident.span()=> __debug_trait_builder};
+            Some(quote_spanned! {input.span=>
+                #[allow(unused_qualifications)]
+                #[allow(clippy::unneeded_field_pattern)]
+                impl #impl_generics #name #ty_generics #where_clause {
+                    #[doc(hidden)]
+                    #[allow(dead_code)]
+                    #vis fn __derivative_debug_fields(&self, #builder: &mut #fmt_path::DebugStruct) {
+                        #truncated_wrapper
+                        #match_self {
+                            #fields_body
+                        }
+                    }
+                }
+            })
+        }
+        _ => None,
+    };
+
     quote_spanned! {input.span=>
         #[allow(unused_qualifications)]
         #[allow(clippy::unneeded_field_pattern)]
         impl #impl_generics #debug_trait_path for #name #ty_generics #where_clause {
             fn fmt(&self, #formatter: &mut #fmt_path::Formatter) -> #fmt_path::Result {
+                #truncated_wrapper
                 #match_self {
                     #body
                 }
             }
         }
+
+        #debug_fields
+    }
+}
+
+/// The body of `fmt` when it doesn't depend on the fields: either the type has a `format_with`
+/// function, or it is a union whose active field is unknown.
+fn whole_type_body(
+    input: &ast::Input,
+    formatter: &proc_macro2::TokenStream,
+) -> Option<proc_macro2::TokenStream> {
+    let fmt_path = paths::fmt_path();
+
+    if let Some(format_fn) = input.attrs.debug_format_with() {
+        Some(utils::call_with(
+            format_fn,
+            quote!(Fn(&Self, &mut #fmt_path::Formatter) -> #fmt_path::Result),
+            quote!(self, #formatter),
+        ))
+    } else if let ast::Body::Union(_) = input.body {
+        let name = input
+            .attrs
+            .debug_name()
+            .map_or_else(|| input.ident.to_string(), syn::LitStr::value);
+        let opaque = format!("{} {{ .. }}", name);
+        Some(quote!(#formatter.write_str(#opaque)))
+    } else {
+        None
     }
 }
 
+/// Whether a variant (or the structure itself) should be shown as its only field. On enumerations,
+/// `transparent` applies to all variants.
+fn is_transparent(input: &ast::Input, attrs: &attr::Input) -> bool {
+    attrs.debug_transparent() || input.attrs.debug_transparent()
+}
+
 fn needs_debug_bound(attrs: &attr::Field) -> bool {
-    !attrs.ignore_debug() && attrs.debug_bound().is_none()
+    !attrs.ignore_debug() && attrs.debug_redact().is_none() && attrs.debug_bound().is_none()
 }
 
 /// Return the path of the `Debug` trait, that is `::std::fmt::Debug`.
@@ -125,12 +430,12 @@ fn debug_trait_path() -> syn::Path {
     }
 }
 
-/// Return the path of the `fmt` module, that is `::std::fmt`.
-fn fmt_path() -> syn::Path {
+/// Return the path of the `IntoIterator` trait, that is `::std::iter::IntoIterator`.
+fn into_iterator_path() -> syn::Path {
     if cfg!(feature = "use_core") {
-        parse_quote!(::core::fmt)
+        parse_quote!(::core::iter::IntoIterator)
     } else {
-        parse_quote!(::std::fmt)
+        parse_quote!(::std::iter::IntoIterator)
     }
 }
 
@@ -143,16 +448,46 @@ fn phantom_path() -> syn::Path {
     }
 }
 
+/// Generate a wrapper used by `max_items` that shows only the first items of a collection.
+fn truncated_wrapper() -> proc_macro2::TokenStream {
+    let debug_trait_path = debug_trait_path();
+    let fmt_path = paths::fmt_path();
+    let into_iterator_path = into_iterator_path();
+
+    quote! {
+        struct __DerivativeTruncated<'a, T: 'a + ?Sized>(&'a T, usize);
+
+        impl<'a, T: ?Sized> #debug_trait_path for __DerivativeTruncated<'a, T>
+        where
+            &'a T: #into_iterator_path,
+            <&'a T as #into_iterator_path>::Item: #debug_trait_path,
+        {
+            fn fmt(&self, __f: &mut #fmt_path::Formatter) -> #fmt_path::Result {
+                let mut __list = __f.debug_list();
+                let mut __iter = #into_iterator_path::into_iter(self.0);
+                for __item in __iter.by_ref().take(self.1) {
+                    let _ = __list.entry(&__item);
+                }
+                let __rest = __iter.count();
+                if __rest > 0 {
+                    let _ = __list.entry(&format_args!("... and {} more", __rest));
+                }
+                __list.finish()
+            }
+        }
+    }
+}
+
 fn format_with(
     f: &ast::Field,
     bounds: &Option<&[syn::WherePredicate]>,
     arg_expr: &proc_macro2::TokenStream,
     arg_ident: &syn::Ident,
-    format_fn: &syn::Path,
+    format_fn: &attr::WithFn,
     mut generics: syn::Generics,
 ) -> proc_macro2::TokenStream {
     let debug_trait_path = debug_trait_path();
-    let fmt_path = fmt_path();
+    let fmt_path = paths::fmt_path();
     let phantom_path = phantom_path();
 
     generics
@@ -207,6 +542,11 @@ fn format_with(
 
     // don't attach a span to prevent issue #58
     let match_self = quote!(match self.0);
+    let format = utils::call_with(
+        format_fn,
+        quote!(Fn(&#ty, &mut #fmt_path::Formatter) -> #fmt_path::Result),
+        quote!(this, __f),
+    );
     quote_spanned!(format_fn.span()=>
         let #arg_ident = {
             struct Dummy #impl_generics (&'_derivative #ty, #phantom_path <(#(#phantom,)*)>) #where_clause;
@@ -214,7 +554,7 @@ fn format_with(
             impl #impl_generics #debug_trait_path for Dummy #ty_generics #where_clause {
                 fn fmt(&self, __f: &mut #fmt_path::Formatter) -> #fmt_path::Result {
                     #match_self {
-                        this => #format_fn(this, __f)
+                        this => #format
                     }
                 }
             }
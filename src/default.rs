@@ -2,18 +2,22 @@ use proc_macro2;
 
 use ast;
 use attr;
+use paths;
 use syn;
+use syn::spanned::Spanned;
 use utils;
 
 /// Derive `Default` for `input`.
-pub fn derive(input: &ast::Input, default: &attr::InputDefault) -> proc_macro2::TokenStream {
+pub fn derive(
+    input: &ast::Input,
+    default: &attr::InputDefault,
+    errors: &mut proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
     fn make_variant_data(
         variant_name: &proc_macro2::TokenStream,
         style: ast::Style,
         fields: &[ast::Field],
     ) -> proc_macro2::TokenStream {
-        let default_trait_path = default_trait_path();
-
         match style {
             ast::Style::Struct => {
                 let mut defaults = Vec::new();
@@ -23,10 +27,7 @@ pub fn derive(input: &ast::Input, default: &attr::InputDefault) -> proc_macro2::
                         .ident
                         .as_ref()
                         .expect("A structure field must have a name");
-                    let default = f
-                        .attrs
-                        .default_value()
-                        .map_or_else(|| quote!(#default_trait_path::default()), |v| quote!(#v));
+                    let default = field_default(f);
 
                     defaults.push(quote!(#name: #default));
                 }
@@ -37,12 +38,7 @@ pub fn derive(input: &ast::Input, default: &attr::InputDefault) -> proc_macro2::
                 let mut defaults = Vec::new();
 
                 for f in fields {
-                    let default = f
-                        .attrs
-                        .default_value()
-                        .map_or_else(|| quote!(#default_trait_path::default()), |v| quote!(#v));
-
-                    defaults.push(default);
+                    defaults.push(field_default(f));
                 }
 
                 quote!(#variant_name ( #(#defaults),* ))
@@ -51,12 +47,23 @@ pub fn derive(input: &ast::Input, default: &attr::InputDefault) -> proc_macro2::
         }
     }
 
+    // a value for the whole type makes the default variant unnecessary
+    if let ast::Body::Enum(ref variants) = input.body {
+        if default.value.is_none() && !check_default_variant(input, variants, errors) {
+            return proc_macro2::TokenStream::new();
+        }
+    }
+
     let name = &input.ident;
-    let default_trait_path = default_trait_path();
+    let default_trait_path = paths::default_trait_path();
     let generics = utils::build_impl_generics(
         input,
         &default_trait_path,
-        |attrs| attrs.default_bound().is_none(),
+        // fields with an explicit default value don't need to implement `Default`, and none do if
+        // the whole type has one
+        |attrs| {
+            default.value.is_none() && attrs.default_bound().is_none() && !has_default_value(attrs)
+        },
         |field| field.default_bound(),
         |input| input.default_bound(),
     );
@@ -81,9 +88,24 @@ pub fn derive(input: &ast::Input, default: &attr::InputDefault) -> proc_macro2::
             quote!(#(#arms),*)
         }
         ast::Body::Struct(style, ref vd) => make_variant_data(&quote!(#name), style, vd),
+        ast::Body::Union(_) => unreachable!("`Default` can't be derived on unions"),
     };
 
-    let new_fn = if default.new {
+    // a value for the whole type replaces the values of its fields
+    let body = match default.value {
+        Some(ref value) => quote!(#value),
+        None => body,
+    };
+
+    let const_impl = if default.is_const {
+        const_default(input, default, &body, errors)
+    } else {
+        None
+    };
+
+    let new_fn = if default.new_with_args {
+        new_with_args(input, default.is_const, errors)
+    } else if default.new && !default.is_const {
         Some(quote!(
             #[allow(unused_qualifications)]
             impl #impl_generics #name #ty_generics #where_clause {
@@ -99,6 +121,8 @@ pub fn derive(input: &ast::Input, default: &attr::InputDefault) -> proc_macro2::
     };
 
     quote!(
+        #const_impl
+
         #new_fn
 
         #[allow(unused_qualifications)]
@@ -110,11 +134,204 @@ pub fn derive(input: &ast::Input, default: &attr::InputDefault) -> proc_macro2::
     )
 }
 
-/// Return the path of the `Default` trait, that is `::std::default::Default`.
-fn default_trait_path() -> syn::Path {
-    if cfg!(feature = "use_core") {
-        parse_quote!(::core::default::Default)
+/// Check that exactly one variant of an enumeration is marked with `Default`.
+fn check_default_variant(
+    input: &ast::Input,
+    variants: &[ast::Variant],
+    errors: &mut proc_macro2::TokenStream,
+) -> bool {
+    let marked: Vec<_> = variants
+        .iter()
+        .filter(|variant| variant.attrs.default.is_some())
+        .map(|variant| format!("`{}`", variant.ident))
+        .collect();
+
+    let message = match marked.len() {
+        1 => return true,
+        0 => format!(
+            "no variant of `{}` is marked with `#[derivative(Default)]`",
+            input.ident
+        ),
+        _ => format!(
+            "only one variant of `{}` can be marked with `#[derivative(Default)]`, found {}",
+            input.ident,
+            marked.join(", ")
+        ),
+    };
+    errors.extend(quote_spanned! {input.ident.span()=>
+        compile_error!(#message);
+    });
+
+    false
+}
+
+/// Whether a field has a default value given with `value` or `with`.
+pub fn has_default_value(attrs: &attr::Field) -> bool {
+    attrs.default_value().is_some() || attrs.default_with().is_some()
+}
+
+/// The default value of a field, that is its `value`, the result of its `with` function or
+/// `Default::default()`.
+pub fn field_default(f: &ast::Field) -> proc_macro2::TokenStream {
+    if let Some(value) = f.attrs.default_value() {
+        quote!(#value)
+    } else if let Some(with) = f.attrs.default_with() {
+        // use the span of the path so that type errors point to it
+        quote_spanned!(with.span()=> #with())
     } else {
-        parse_quote!(::std::default::Default)
+        let default_trait_path = paths::default_trait_path();
+        quote!(#default_trait_path::default())
     }
 }
+
+/// Generate the `DEFAULT` constant, and a `const fn new` if asked for.
+fn const_default(
+    input: &ast::Input,
+    default: &attr::InputDefault,
+    body: &proc_macro2::TokenStream,
+    errors: &mut proc_macro2::TokenStream,
+) -> Option<proc_macro2::TokenStream> {
+    let fields: Vec<&ast::Field> = match input.body {
+        ast::Body::Enum(ref variants) => variants
+            .iter()
+            .filter(|variant| variant.attrs.default.is_some())
+            .flat_map(|variant| &variant.fields)
+            .collect(),
+        ast::Body::Struct(_, ref fields) => fields.iter().collect(),
+        ast::Body::Union(_) => unreachable!("`Default` can't be derived on unions"),
+    };
+
+    let missing_values: Vec<&ast::Field> = if default.value.is_some() {
+        Vec::new()
+    } else {
+        fields
+            .into_iter()
+            .filter(|field| !has_default_value(&field.attrs))
+            .collect()
+    };
+    if !missing_values.is_empty() {
+        // with `new="args"`, they are arguments of the `const fn new` instead
+        if !default.new_with_args {
+            for field in missing_values {
+                errors.extend(quote_spanned! {field.span=>
+                    compile_error!("`Default=\"const\"` needs a `value` or `with` for each field");
+                });
+            }
+        }
+        return None;
+    }
+
+    let new_fn = if default.new && !default.new_with_args {
+        Some(quote!(
+            /// Creates a default value for this type.
+            #[inline]
+            pub const fn new() -> Self {
+                Self::DEFAULT
+            }
+        ))
+    } else {
+        None
+    };
+
+    // all the fields have a value, they don't need to implement `Default`
+    let name = &input.ident;
+    let generics = utils::build_impl_generics(
+        input,
+        &paths::default_trait_path(),
+        |_| false,
+        |field| field.default_bound(),
+        |input| input.default_bound(),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Some(quote!(
+        #[allow(unused_qualifications)]
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// The default value for this type.
+            pub const DEFAULT: Self = #body;
+
+            #new_fn
+        }
+    ))
+}
+
+/// Generate a `new` function taking the fields that are not filled automatically as arguments.
+fn new_with_args(
+    input: &ast::Input,
+    is_const: bool,
+    errors: &mut proc_macro2::TokenStream,
+) -> Option<proc_macro2::TokenStream> {
+    let (style, fields) = match input.body {
+        ast::Body::Struct(style, ref fields) => (style, fields),
+        ast::Body::Enum(_) | ast::Body::Union(_) => {
+            errors.extend(quote_spanned! {input.ident.span()=>
+                compile_error!("`Default(new=\"args\")` can only be used on structures");
+            });
+            return None;
+        }
+    };
+
+    let mut args = Vec::new();
+    let mut values = Vec::new();
+    let mut has_const_error = false;
+    for (i, f) in fields.iter().enumerate() {
+        let value = if f.attrs.default_skip() {
+            if is_const && !has_default_value(&f.attrs) {
+                errors.extend(quote_spanned! {f.span=>
+                    compile_error!("`Default=\"const\"` needs a `value` or `with` for each field that is not an argument of `new`");
+                });
+                has_const_error = true;
+            }
+            field_default(f)
+        } else {
+            let arg = f.ident.clone().unwrap_or_else(|| {
+                syn::Ident::new(&format!("arg{}", i), proc_macro2::Span::call_site())
+            });
+            let ty = f.ty;
+            args.push(quote!(#arg: #ty));
+            quote!(#arg)
+        };
+
+        values.push(match f.ident {
+            Some(ref name) => quote!(#name: #value),
+            None => value,
+        });
+    }
+
+    if has_const_error {
+        return None;
+    }
+
+    let name = &input.ident;
+    let body = match style {
+        ast::Style::Struct => quote!(#name { #(#values),* }),
+        ast::Style::Tuple => quote!(#name ( #(#values),* )),
+        ast::Style::Unit => quote!(#name),
+    };
+
+    // only the fields filled automatically need to implement `Default`
+    let generics = utils::build_impl_generics(
+        input,
+        &paths::default_trait_path(),
+        |attrs| {
+            attrs.default_skip() && !has_default_value(attrs) && attrs.default_bound().is_none()
+        },
+        |field| field.default_bound(),
+        |input| input.default_bound(),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let constness = if is_const { Some(quote!(const)) } else { None };
+
+    Some(quote!(
+        #[allow(unused_qualifications)]
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Creates a value for this type from its fields without default values.
+            #[inline]
+            #[allow(clippy::too_many_arguments, clippy::redundant_field_names)]
+            pub #constness fn new(#(#args),*) -> Self {
+                #body
+            }
+        }
+    ))
+}
@@ -0,0 +1,264 @@
+use proc_macro2;
+
+use ast;
+use attr;
+use matcher;
+use paths;
+use std::iter::Peekable;
+use std::ptr;
+use std::str::Chars;
+use syn;
+use utils;
+
+/// A `Display` format string where every argument has been replaced by the binding of the field
+/// it refers to, eg. `"{foo} at {1:x}"` becomes `"{__arg_0} at {__arg_1:x}"`.
+struct Template {
+    format: String,
+    /// Indices of the fields used by the format string, without duplicates.
+    fields: Vec<usize>,
+}
+
+/// Derive `Display` for `input`.
+pub fn derive(
+    input: &ast::Input,
+    errors: &mut proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let variants: Vec<(&syn::Ident, &attr::Input, ast::Style, &[ast::Field])> = match input.body {
+        ast::Body::Enum(ref variants) => variants
+            .iter()
+            .map(|v| (&v.ident, &v.attrs, v.style, v.fields.as_slice()))
+            .collect(),
+        ast::Body::Struct(style, ref fields) => {
+            vec![(&input.ident, &input.attrs, style, fields.as_slice())]
+        }
+        ast::Body::Union(_) => unreachable!("`Display` can't be derived on unions"),
+    };
+
+    let mut templates = Vec::with_capacity(variants.len());
+    let mut used_fields = Vec::new();
+    let mut has_errors = false;
+    for (name, attrs, style, fields) in variants {
+        let format = match attrs.display_fmt().or_else(|| input.attrs.display_fmt()) {
+            Some(format) => format,
+            None => {
+                let message = format!(
+                    "missing format string for `{}`, use `#[derivative(Display=\"…\")]`",
+                    name
+                );
+                errors.extend(quote_spanned! {name.span()=>
+                    compile_error!(#message);
+                });
+                has_errors = true;
+                continue;
+            }
+        };
+
+        match parse_template(&format.value(), style, fields) {
+            Ok(template) => {
+                used_fields.extend(template.fields.iter().map(|&i| &fields[i].attrs));
+                templates.push(template);
+            }
+            Err(message) => {
+                let message = format!("invalid `Display` format string: {}", message);
+                errors.extend(quote_spanned! {format.span()=>
+                    compile_error!(#message);
+                });
+                has_errors = true;
+            }
+        }
+    }
+
+    if has_errors {
+        return proc_macro2::TokenStream::new();
+    }
+
+    let is_used = |attrs: &attr::Field| used_fields.iter().any(|&used| ptr::eq(used, attrs));
+
+    let body = matcher::Matcher::new(matcher::BindingStyle::Ref, input.attrs.is_packed)
+        .with_field_filter(|f: &ast::Field| is_used(&f.attrs))
+        .build_arms(input, "__arg", |_, n, _, _, _, bis| {
+            let template = &templates[n];
+            let format = &template.format;
+            let args = template.fields.iter().map(|&i| {
+                let arg_ident = &bis[i].ident;
+                let arg_expr = &bis[i].expr;
+
+                quote!(#arg_ident = #arg_expr)
+            });
+
+            quote! {
+                __f.write_fmt(format_args!(#format, #(#args),*))
+            }
+        });
+
+    let name = &input.ident;
+    let display_trait_path = display_trait_path();
+    let fmt_path = paths::fmt_path();
+    let generics = utils::build_impl_generics(
+        input,
+        &display_trait_path,
+        is_used,
+        |_| None,
+        |input| input.display_bound(),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // don't attach a span to prevent issue #58
+    let match_self = quote!(match *self);
+    quote_spanned! {input.span=>
+        #[allow(unused_qualifications)]
+        #[allow(clippy::unneeded_field_pattern)]
+        impl #impl_generics #display_trait_path for #name #ty_generics #where_clause {
+            fn fmt(&self, __f: &mut #fmt_path::Formatter) -> #fmt_path::Result {
+                #match_self {
+                    #body
+                }
+            }
+        }
+    }
+}
+
+/// Parse a format string, replacing every argument by the binding of the corresponding field.
+/// Fields can be referred to by name, or by position for tuple fields.
+fn parse_template(
+    template: &str,
+    style: ast::Style,
+    fields: &[ast::Field],
+) -> Result<Template, String> {
+    let mut format = String::with_capacity(template.len());
+    let mut used = Vec::new();
+    let mut next_positional = 0;
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                format.push_str("{{");
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                format.push_str("}}");
+            }
+            '{' => {
+                let argument = read_argument(&mut chars)?;
+                let (name, spec) = match argument.find(':') {
+                    Some(i) => (&argument[..i], Some(&argument[i + 1..])),
+                    None => (&argument[..], None),
+                };
+
+                let index = if name.is_empty() {
+                    next_positional += 1;
+                    resolve_field(&(next_positional - 1).to_string(), style, fields)?
+                } else {
+                    resolve_field(name, style, fields)?
+                };
+
+                format.push('{');
+                push_binding(&mut format, &mut used, index);
+                if let Some(spec) = spec {
+                    format.push(':');
+                    push_spec(&mut format, &mut used, spec, style, fields)?;
+                }
+                format.push('}');
+            }
+            '}' => return Err("unmatched `}` found".into()),
+            c => format.push(c),
+        }
+    }
+
+    Ok(Template {
+        format,
+        fields: used,
+    })
+}
+
+/// Read the content of a `{…}` argument, after its opening brace.
+fn read_argument<'a>(chars: &mut Peekable<Chars<'a>>) -> Result<String, String> {
+    let mut argument = String::new();
+
+    loop {
+        match chars.next() {
+            Some('}') => return Ok(argument),
+            Some(c) => argument.push(c),
+            None => return Err("expected `}` but string was terminated".into()),
+        }
+    }
+}
+
+/// Copy the format spec of an argument, replacing the `width$` and `precision$` parameters.
+fn push_spec(
+    format: &mut String,
+    used: &mut Vec<usize>,
+    spec: &str,
+    style: ast::Style,
+    fields: &[ast::Field],
+) -> Result<(), String> {
+    let mut rest = spec;
+
+    while let Some(dollar) = rest.find('$') {
+        let before = &rest[..dollar];
+        let start = before
+            .char_indices()
+            .rev()
+            .find(|&(_, c)| !(c.is_alphanumeric() || c == '_'))
+            .map_or(0, |(i, c)| i + c.len_utf8());
+        // a `0` flag can be directly followed by a named width, as in `{:0width$}`
+        let start = match before[start..].find(|c: char| !c.is_ascii_digit()) {
+            Some(i) if i > 0 => start + i,
+            _ => start,
+        };
+
+        format.push_str(&before[..start]);
+        let index = resolve_field(&before[start..], style, fields)?;
+        push_binding(format, used, index);
+        format.push('$');
+
+        rest = &rest[dollar + 1..];
+    }
+
+    format.push_str(rest);
+
+    Ok(())
+}
+
+/// Find the index of the field referred to by `name`.
+fn resolve_field(name: &str, style: ast::Style, fields: &[ast::Field]) -> Result<usize, String> {
+    if let Ok(index) = name.parse::<usize>() {
+        return match style {
+            ast::Style::Tuple if index < fields.len() => Ok(index),
+            ast::Style::Tuple => Err(format!("there is no field `{}`", index)),
+            ast::Style::Struct | ast::Style::Unit => Err(format!(
+                "positional argument `{}` can only be used with tuple fields",
+                index
+            )),
+        };
+    }
+
+    fields
+        .iter()
+        .position(|f| {
+            f.ident.as_ref().map_or(false, |ident| {
+                let ident = ident.to_string();
+                ident.trim_start_matches("r#") == name
+            })
+        })
+        .ok_or_else(|| format!("there is no field `{}`", name))
+}
+
+fn push_binding(format: &mut String, used: &mut Vec<usize>, index: usize) {
+    format.push_str(&format!("__arg_{}", index));
+
+    if !used.contains(&index) {
+        used.push(index);
+    }
+}
+
+/// Return the path of the `Display` trait, that is `::std::fmt::Display`.
+fn display_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::fmt::Display)
+    } else {
+        parse_quote!(::std::fmt::Display)
+    }
+}
@@ -20,6 +20,7 @@ pub fn derive(input: &ast::Input) -> proc_macro2::TokenStream {
         None
     };
 
+    let hasher_ty_parameter = utils::hygienic_type_parameter(input, "__H");
     let body = matcher::Matcher::new(matcher::BindingStyle::Ref, input.attrs.is_packed).build_arms(
         input,
         "__arg",
@@ -32,8 +33,14 @@ pub fn derive(input: &ast::Input) -> proc_macro2::TokenStream {
                 let arg = &bi.expr;
 
                 if let Some(hash_with) = bi.field.attrs.hash_with() {
+                    let ty = bi.field.ty;
+                    let hash_with = utils::call_with(
+                        hash_with,
+                        quote!(Fn(&#ty, &mut #hasher_ty_parameter)),
+                        quote!(&#arg, __state),
+                    );
                     Some(quote! {
-                        #hash_with(&#arg, __state);
+                        #hash_with;
                     })
                 } else {
                     Some(quote! {
@@ -58,7 +65,6 @@ pub fn derive(input: &ast::Input) -> proc_macro2::TokenStream {
     );
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let hasher_ty_parameter = utils::hygienic_type_parameter(input, "__H");
     quote! {
         #[allow(unused_qualifications)]
         impl #impl_generics #hash_trait_path for #name #ty_generics #where_clause {
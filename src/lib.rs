@@ -4,6 +4,8 @@
 #![allow(clippy::unknown_clippy_lints)] // because of other #![allow]s
 #![allow(clippy::mem_replace_with_default)] // needs rustc 1.40
 #![allow(clippy::option_as_ref_deref)] // needs rustc 1.40
+#![allow(clippy::match_like_matches_macro)] // needs rustc 1.42
+#![allow(clippy::unnecessary_map_or)] // needs rustc 1.70
 #![allow(clippy::cyclomatic_complexity)] // old name of cognitive_complexity
 #![allow(clippy::cognitive_complexity)] // in code generated by macros
 #![allow(clippy::redundant_closure)]
@@ -19,10 +21,12 @@ extern crate quote;
 mod ast;
 mod attr;
 mod bound;
+mod builder;
 mod clone;
 mod cmp;
 mod debug;
 mod default;
+mod display;
 mod hash;
 mod matcher;
 mod paths;
@@ -36,17 +40,50 @@ fn derive_impls(
 ) -> proc_macro2::TokenStream {
     let mut tokens = proc_macro2::TokenStream::new();
 
+    if let ast::Body::Union(_) = input.body {
+        let unsupported = [
+            ("Builder", input.attrs.builder.is_some()),
+            ("Default", input.attrs.default.is_some()),
+            ("Display", input.attrs.display.is_some()),
+            ("Eq", input.attrs.eq.is_some()),
+            ("Hash", input.attrs.hash.is_some()),
+            ("PartialEq", input.attrs.partial_eq.is_some()),
+            ("PartialOrd", input.attrs.partial_ord.is_some()),
+            ("Ord", input.attrs.ord.is_some()),
+        ];
+        let mut has_unsupported = false;
+        for &(name, _) in unsupported.iter().filter(|&&(_, derived)| derived) {
+            let message = format!(
+                "derivative can't derive `{}` on unions, only `Clone`, `Copy` and `Debug`",
+                name
+            );
+            errors.extend(quote_spanned! {input.ident.span()=>
+                compile_error!(#message);
+            });
+            has_unsupported = true;
+        }
+        if has_unsupported {
+            return std::mem::replace(errors, Default::default());
+        }
+    }
+
+    if input.attrs.builder.is_some() {
+        tokens.extend(builder::derive(input, errors));
+    }
     if input.attrs.clone.is_some() {
-        tokens.extend(clone::derive_clone(input));
+        tokens.extend(clone::derive_clone(input, errors));
     }
     if input.attrs.copy.is_some() {
         tokens.extend(clone::derive_copy(input));
     }
     if input.attrs.debug.is_some() {
-        tokens.extend(debug::derive(input));
+        tokens.extend(debug::derive(input, errors));
     }
     if let Some(ref default) = input.attrs.default {
-        tokens.extend(default::derive(input, default));
+        tokens.extend(default::derive(input, default, errors));
+    }
+    if input.attrs.display.is_some() {
+        tokens.extend(display::derive(input, errors));
     }
     if input.attrs.eq.is_some() {
         tokens.extend(cmp::derive_eq(input));
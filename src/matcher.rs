@@ -231,6 +231,7 @@ impl<T: Fn (&ast::Field) -> bool> Matcher<T> {
                     self.build_match_pattern_impl(ident, style, vd, binding_name),
                 )]
             }
+            ast::Body::Union(_) => unreachable!("unions can't be matched on"),
         }
     }
 
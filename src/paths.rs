@@ -1,5 +1,14 @@
 //! Contains some standard paths.
 
+/// Return the path of the `Default` trait, that is `::std::default::Default`.
+pub fn default_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::default::Default)
+    } else {
+        parse_quote!(::std::default::Default)
+    }
+}
+
 /// Return the path of the `discriminant` function, that is `::std::mem::discriminant`.
 pub fn discriminant_path() -> syn::Path {
     if cfg!(feature = "use_core") {
@@ -7,4 +16,31 @@ pub fn discriminant_path() -> syn::Path {
     } else {
         parse_quote!(::std::mem::discriminant)
     }
-}
\ No newline at end of file
+}
+
+/// Return the path of the `fmt` module, that is `::std::fmt`.
+pub fn fmt_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::fmt)
+    } else {
+        parse_quote!(::std::fmt)
+    }
+}
+
+/// Return the path of the `Option` type, that is `::std::option::Option`.
+pub fn option_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::option::Option)
+    } else {
+        parse_quote!(::std::option::Option)
+    }
+}
+
+/// Return the path of the `Result` type, that is `::std::result::Result`.
+pub fn result_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::result::Result)
+    } else {
+        parse_quote!(::std::result::Result)
+    }
+}
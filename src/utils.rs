@@ -41,3 +41,19 @@ pub fn hygienic_type_parameter(item: &ast::Input, base: &str) -> syn::Ident {
 
     syn::Ident::new(&typaram, proc_macro2::Span::call_site())
 }
+
+/// Call the function given to a `*_with` attribute. Expressions are first bound to a trait object
+/// with the expected `signature` so that closure arguments get their types from the field.
+pub fn call_with(
+    with: &attr::WithFn,
+    signature: proc_macro2::TokenStream,
+    args: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    match *with {
+        attr::WithFn::Path(ref path) => quote!(#path(#args)),
+        attr::WithFn::Expr(ref expr) => quote!({
+            let __derivative_with: &dyn #signature = &(#expr);
+            __derivative_with(#args)
+        }),
+    }
+}
@@ -0,0 +1,24 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Builder)]
+struct Foo(u8);
+
+#[derive(Derivative)]
+#[derivative(Builder)]
+enum Bar {
+    A { a: u8 },
+}
+
+#[derive(Derivative)]
+#[derivative(Builder)]
+struct Job {
+    name: String,
+    build: u32,
+}
+
+fn main() {}
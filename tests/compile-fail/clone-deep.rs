@@ -0,0 +1,33 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+type Shared = Rc<u8>;
+
+#[derive(Derivative)]
+#[derivative(Clone)]
+struct Foo {
+    #[derivative(Clone="deep")]
+    foo: Box<dyn Debug>,
+    #[derivative(Clone="deep")]
+    bar: Option<Box<Rc<dyn Debug>>>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone)]
+struct Bar {
+    #[derivative(Clone="deep")]
+    alias: Shared,
+    #[derivative(Clone="deep")]
+    map: HashMap<u8, Rc<u8>>,
+    #[derivative(Clone="deep")]
+    string: String,
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Clone)]
+struct Foo {
+    #[derivative(Clone(default="true", value="42"))]
+    foo: u8,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone, Copy)]
+struct Counter {
+    value: u32,
+    #[derivative(Clone="default")]
+    clones: u32,
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+fn assign(target: &mut u8, source: &u8) {
+    *target = *source;
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(clone_from="true"))]
+struct Foo {
+    #[derivative(Clone(clone_from="false", clone_from_with="assign"))]
+    foo: u8,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone)]
+struct Bar {
+    #[derivative(Clone(clone_from_with="assign"))]
+    bar: u8,
+    #[derivative(Clone(clone_from="false"))]
+    baz: u8,
+}
+
+fn main() {}
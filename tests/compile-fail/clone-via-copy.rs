@@ -0,0 +1,14 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Clone="via_copy", Copy)]
+struct Foo<T> {
+    #[derivative(Clone="default")]
+    foo: T,
+}
+
+fn main() {}
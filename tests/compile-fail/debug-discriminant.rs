@@ -0,0 +1,18 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug="discriminant")]
+enum Foo {
+    Bar,
+    Baz(u8),
+}
+
+#[derive(Derivative)]
+#[derivative(Debug="discriminant")]
+struct Qux;
+
+fn main() {}
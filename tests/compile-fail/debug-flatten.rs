@@ -0,0 +1,28 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug="flattenable")]
+struct Inner {
+    foo: u8,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Tuple(#[derivative(Debug="flatten")] Inner);
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Renamed {
+    #[derivative(Debug(flatten="true", rename="bar"))]
+    inner: Inner,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug="flattenable")]
+struct FlattenableTuple(u8);
+
+fn main() {}
@@ -0,0 +1,14 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug(name="Mode"))]
+enum __Mode {
+    Fast,
+    Slow,
+}
+
+fn main() {}
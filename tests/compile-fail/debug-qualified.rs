@@ -0,0 +1,20 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug="qualified")]
+struct Foo {
+    foo: u8,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+enum Bar {
+    #[derivative(Debug="qualified")]
+    Baz,
+}
+
+fn main() {}
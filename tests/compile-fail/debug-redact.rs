@@ -0,0 +1,20 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Foo {
+    #[derivative(Debug(redact="true", format="{:x}"))]
+    foo: u8,
+    #[derivative(Debug(redact="true", format_with="std::fmt::Debug::fmt"))]
+    bar: u8,
+    #[derivative(Debug(redact="true", max_items="2"))]
+    baz: Vec<u8>,
+    #[derivative(Debug(redact="false", max_items="2"))]
+    qux: Vec<u8>,
+}
+
+fn main() {}
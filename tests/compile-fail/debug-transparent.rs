@@ -0,0 +1,28 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug="transparent")]
+struct Foo(u8, u8);
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+enum Bar {
+    #[derivative(Debug="transparent")]
+    Unit,
+    #[derivative(Debug="transparent")]
+    Pair(u8, u8),
+    #[derivative(Debug="transparent")]
+    Ignored(#[derivative(Debug="ignore")] u8),
+    #[derivative(Debug="transparent")]
+    Ok(u8),
+}
+
+#[derive(Derivative)]
+#[derivative(Debug="transparent")]
+struct Baz(#[derivative(Debug(skip_if="Option::is_none"))] Option<u8>);
+
+fn main() {}
@@ -0,0 +1,23 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Default="const")]
+struct Foo {
+    #[derivative(Default(value="42"))]
+    foo: u8,
+    bar: Vec<u8>,
+}
+
+#[derive(Derivative)]
+#[derivative(Default(const="true", new="args"))]
+struct Bar {
+    foo: u8,
+    #[derivative(Default="skip")]
+    bar: Vec<u8>,
+}
+
+fn main() {}
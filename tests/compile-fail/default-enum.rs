@@ -0,0 +1,24 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Default)]
+enum Unmarked {
+    A,
+    B,
+}
+
+#[derive(Derivative)]
+#[derivative(Default(new="true"))]
+enum Several {
+    #[derivative(Default)]
+    A,
+    B,
+    #[derivative(Default)]
+    C(u8),
+}
+
+fn main() {}
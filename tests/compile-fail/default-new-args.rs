@@ -0,0 +1,15 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Default(new="args"))]
+enum Foo {
+    #[derivative(Default)]
+    A,
+    B(u8),
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+fn answer() -> u8 {
+    42
+}
+
+#[derive(Derivative)]
+#[derivative(Default)]
+struct Foo {
+    #[derivative(Default(value="42", with="answer"))]
+    foo: u8,
+    #[derivative(Default(with="|| 42"))]
+    bar: u8,
+    #[derivative(Default(with="answer"))]
+    baz: String,
+}
+
+fn main() {}
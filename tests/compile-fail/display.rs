@@ -0,0 +1,31 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Display)]
+struct NoFormat;
+
+#[derive(Derivative)]
+#[derivative(Display="{bar}")]
+struct UnknownField {
+    foo: u8,
+}
+
+#[derive(Derivative)]
+#[derivative(Display="{0}")]
+struct Positional {
+    foo: u8,
+}
+
+#[derive(Derivative)]
+#[derivative(Display)]
+enum Enum {
+    #[derivative(Display="A")]
+    A,
+    B,
+}
+
+fn main() {}
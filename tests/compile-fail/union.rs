@@ -0,0 +1,14 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Clone, Copy, PartialEq, Hash)]
+union Foo {
+    a: u32,
+    b: f32,
+}
+
+fn main() {}
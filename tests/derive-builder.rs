@@ -0,0 +1,55 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Builder, Default)]
+pub struct Foo {
+    foo: u8,
+    #[derivative(Default(value="42"))]
+    bar: u8,
+    #[derivative(Default="skip")]
+    baz: Vec<u8>,
+    name: String,
+}
+
+struct NonDefault;
+
+#[derive(Derivative)]
+#[derivative(Builder)]
+struct Generic<'a, T, U> where T: 'a {
+    required: &'a T,
+    #[derivative(Default="skip")]
+    skipped: Option<U>,
+    #[derivative(Default(value="Vec::new()"))]
+    with_value: Vec<T>,
+}
+
+#[derive(Derivative)]
+#[derivative(Builder(bound=""))]
+struct Bound<T> {
+    #[derivative(Default(value="None"))]
+    value: Option<T>,
+}
+
+#[test]
+fn main() {
+    let foo = Foo::builder().foo(1).name("name".to_string()).build();
+    assert_eq!(foo, Ok(Foo { foo: 1, bar: 42, baz: vec![], name: "name".to_string() }));
+
+    let foo = Foo::builder().name("name".to_string()).bar(2).baz(vec![3]).foo(1).build();
+    assert_eq!(foo, Ok(Foo { foo: 1, bar: 2, baz: vec![3], name: "name".to_string() }));
+
+    assert_eq!(Foo::builder().foo(1).build(), Err("missing field `name`"));
+    assert_eq!(Foo::builder().build(), Err("missing field `foo`"));
+
+    let required = NonDefault;
+    let generic = Generic::<NonDefault, u8>::builder().required(&required).build().unwrap();
+    let Generic { required: &NonDefault, skipped, with_value } = generic;
+    assert!(skipped.is_none());
+    assert!(with_value.is_empty());
+
+    assert!(Bound::<NonDefault>::builder().build().unwrap().value.is_none());
+}
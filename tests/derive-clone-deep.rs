@@ -0,0 +1,94 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[derive(Debug, Derivative)]
+#[derivative(Clone(clone_from="true"))]
+struct Snapshot<T> {
+    #[derivative(Clone="deep")]
+    shared: Rc<RefCell<Vec<u8>>>,
+    #[derivative(Clone(deep="true"))]
+    optional: Option<Rc<T>>,
+    #[derivative(Clone="deep")]
+    list: Vec<Arc<String>>,
+    #[derivative(Clone="deep")]
+    boxed: Box<Rc<u8>>,
+    shallow: Rc<u8>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone)]
+struct Tuple(#[derivative(Clone="deep")] std::rc::Rc<Vec<Rc<u8>>>);
+
+#[derive(Derivative)]
+#[derivative(Clone)]
+struct Unsized {
+    #[derivative(Clone="deep")]
+    name: Arc<str>,
+    #[derivative(Clone="deep")]
+    bytes: Rc<[u8]>,
+    #[derivative(Clone="deep")]
+    boxed: Box<str>,
+    #[derivative(Clone="deep")]
+    names: Vec<Option<Rc<str>>>,
+}
+
+#[test]
+fn main() {
+    let snapshot = Snapshot {
+        shared: Rc::new(RefCell::new(vec![1])),
+        optional: Some(Rc::new("foo")),
+        list: vec![Arc::new("bar".to_string())],
+        boxed: Box::new(Rc::new(2)),
+        shallow: Rc::new(4),
+    };
+    let clone = snapshot.clone();
+
+    clone.shared.borrow_mut().push(2);
+    assert_eq!(*snapshot.shared.borrow(), vec![1]);
+    assert_eq!(*clone.shared.borrow(), vec![1, 2]);
+    assert!(!Rc::ptr_eq(clone.optional.as_ref().unwrap(), snapshot.optional.as_ref().unwrap()));
+    assert_eq!(**clone.optional.as_ref().unwrap(), "foo");
+    assert!(!Arc::ptr_eq(&clone.list[0], &snapshot.list[0]));
+    assert_eq!(*clone.list[0], "bar");
+    assert!(!Rc::ptr_eq(&clone.boxed, &snapshot.boxed));
+    assert!(Rc::ptr_eq(&clone.shallow, &snapshot.shallow));
+
+    let mut target = Snapshot {
+        shared: Rc::new(RefCell::new(vec![])),
+        optional: None,
+        list: vec![],
+        boxed: Box::new(Rc::new(0)),
+        shallow: Rc::new(0),
+    };
+    target.clone_from(&snapshot);
+    assert!(!Rc::ptr_eq(&target.shared, &snapshot.shared));
+    assert_eq!(*target.shared.borrow(), vec![1]);
+    assert_eq!(target.list.len(), 1);
+
+    let tuple = Tuple(Rc::new(vec![Rc::new(5)]));
+    let clone = tuple.clone();
+    assert!(!Rc::ptr_eq(&clone.0, &tuple.0));
+    assert!(!Rc::ptr_eq(&clone.0[0], &tuple.0[0]));
+
+    let unsized_value = Unsized {
+        name: Arc::from("name"),
+        bytes: Rc::from(&[1, 2][..]),
+        boxed: Box::from("boxed"),
+        names: vec![Some(Rc::from("foo")), None],
+    };
+    let clone = unsized_value.clone();
+    assert!(!Arc::ptr_eq(&clone.name, &unsized_value.name));
+    assert_eq!(&*clone.name, "name");
+    assert!(!Rc::ptr_eq(&clone.bytes, &unsized_value.bytes));
+    assert_eq!(&*clone.bytes, &[1, 2]);
+    assert_eq!(&*clone.boxed, "boxed");
+    assert!(!Rc::ptr_eq(clone.names[0].as_ref().unwrap(), unsized_value.names[0].as_ref().unwrap()));
+    assert!(clone.names[1].is_none());
+}
@@ -0,0 +1,81 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+fn next_id() -> usize {
+    NEXT_ID.fetch_add(1, Ordering::SeqCst)
+}
+
+struct NotClone;
+
+#[derive(Derivative)]
+#[derivative(Clone(clone_from="true"))]
+struct Cached {
+    data: Vec<u8>,
+    #[derivative(Clone="default")]
+    hits: Cell<u32>,
+    #[derivative(Clone(value="next_id()"))]
+    id: usize,
+    #[derivative(Clone(default="true"))]
+    cache: Option<String>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone)]
+struct Tuple(u8, #[derivative(Clone(value="None"))] Option<NotClone>);
+
+#[derive(Derivative)]
+#[derivative(Clone)]
+enum Slot<T> {
+    Empty,
+    Full(u8, #[derivative(Clone="default")] T),
+}
+
+#[derive(Default)]
+struct DefaultOnly(u8);
+
+#[test]
+fn main() {
+    let cached = Cached {
+        data: vec![1, 2],
+        hits: Cell::new(3),
+        id: next_id(),
+        cache: Some("foo".to_string()),
+    };
+    let clone = cached.clone();
+    assert_eq!(clone.data, vec![1, 2]);
+    assert_eq!(clone.hits.get(), 0);
+    assert_ne!(clone.id, cached.id);
+    assert_eq!(clone.cache, None);
+
+    let mut target = Cached {
+        data: vec![],
+        hits: Cell::new(1),
+        id: 0,
+        cache: None,
+    };
+    target.clone_from(&cached);
+    assert_eq!(target.data, vec![1, 2]);
+    assert_eq!(target.hits.get(), 0);
+    assert_ne!(target.id, cached.id);
+
+    let tuple = Tuple(1, Some(NotClone)).clone();
+    assert_eq!(tuple.0, 1);
+    assert!(tuple.1.is_none());
+
+    match Slot::Full(1, DefaultOnly(2)).clone() {
+        Slot::Full(a, DefaultOnly(b)) => assert_eq!((a, b), (1, 0)),
+        Slot::Empty => panic!(),
+    }
+    assert!(match Slot::Empty::<DefaultOnly>.clone() {
+        Slot::Empty => true,
+        Slot::Full(..) => false,
+    });
+}
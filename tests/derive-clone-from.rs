@@ -0,0 +1,106 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Debug, PartialEq)]
+struct Tracked(u8, &'static str);
+
+impl Clone for Tracked {
+    fn clone(&self) -> Self {
+        Tracked(self.0, "clone")
+    }
+
+    fn clone_from(&mut self, other: &Self) {
+        *self = Tracked(other.0, "clone_from");
+    }
+}
+
+fn double(t: &Tracked) -> Tracked {
+    Tracked(t.0 * 2, "clone_with")
+}
+
+fn assign(target: &mut Tracked, source: &Tracked) {
+    *target = Tracked(source.0 + 1, "clone_from_with");
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Clone(clone_from="true"))]
+struct Foo {
+    default: Tracked,
+    #[derivative(Clone="clone_from")]
+    explicit: Tracked,
+    #[derivative(Clone(clone_from="false"))]
+    fresh: Tracked,
+    #[derivative(Clone(clone_with="double"))]
+    with: Tracked,
+    #[derivative(Clone(clone_from_with="assign"))]
+    from_with: Tracked,
+    #[derivative(Clone(clone_from_with="|a, b| *a = Tracked(b.0, \"closure\")"))]
+    closure: Tracked,
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Clone(clone_from="true"))]
+enum Bar {
+    A(#[derivative(Clone(clone_from="false"))] Tracked),
+    B {
+        #[derivative(Clone(clone_with="double", clone_from_with="assign"))]
+        b: Tracked,
+    },
+}
+
+fn foo(n: u8) -> Foo {
+    Foo {
+        default: Tracked(n, ""),
+        explicit: Tracked(n, ""),
+        fresh: Tracked(n, ""),
+        with: Tracked(n, ""),
+        from_with: Tracked(n, ""),
+        closure: Tracked(n, ""),
+    }
+}
+
+#[test]
+fn main() {
+    let source = foo(1);
+
+    assert_eq!(
+        source.clone(),
+        Foo {
+            default: Tracked(1, "clone"),
+            explicit: Tracked(1, "clone"),
+            fresh: Tracked(1, "clone"),
+            with: Tracked(2, "clone_with"),
+            from_with: Tracked(1, "clone"),
+            closure: Tracked(1, "clone"),
+        }
+    );
+
+    let mut target = foo(0);
+    target.clone_from(&source);
+    assert_eq!(
+        target,
+        Foo {
+            default: Tracked(1, "clone_from"),
+            explicit: Tracked(1, "clone_from"),
+            fresh: Tracked(1, "clone"),
+            with: Tracked(2, "clone_with"),
+            from_with: Tracked(2, "clone_from_with"),
+            closure: Tracked(1, "closure"),
+        }
+    );
+
+    let mut target = Bar::A(Tracked(0, ""));
+    target.clone_from(&Bar::A(Tracked(1, "")));
+    assert_eq!(target, Bar::A(Tracked(1, "clone")));
+
+    let mut target = Bar::B { b: Tracked(0, "") };
+    target.clone_from(&Bar::B { b: Tracked(1, "") });
+    assert_eq!(target, Bar::B { b: Tracked(2, "clone_from_with") });
+
+    let mut target = Bar::A(Tracked(0, ""));
+    target.clone_from(&Bar::B { b: Tracked(1, "") });
+    assert_eq!(target, Bar::B { b: Tracked(2, "clone_with") });
+}
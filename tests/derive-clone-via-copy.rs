@@ -0,0 +1,55 @@
+#![allow(clippy::clone_on_copy)]
+
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::marker::PhantomData;
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Clone="via_copy", Copy)]
+struct Point<T> {
+    x: T,
+    y: T,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(via_copy="true"), Copy)]
+enum Shape<T, U> {
+    Dot(Point<T>),
+    Line { from: Point<T>, to: Point<T> },
+    Marker(PhantomData<U>),
+}
+
+struct NotCopy;
+
+#[derive(Derivative)]
+#[derivative(Clone(via_copy="true", bound="T: Copy"), Copy(bound="T: Copy"))]
+struct Raw<T> {
+    ptr: *const T,
+}
+
+#[test]
+fn main() {
+    let point = Point { x: 1, y: 2 };
+    assert_eq!(point.clone(), Point { x: 1, y: 2 });
+
+    let line: Shape<u8, NotCopy> = Shape::Line {
+        from: Point { x: 1, y: 2 },
+        to: Point { x: 3, y: 4 },
+    };
+    match line.clone() {
+        Shape::Line { from, to } => assert_eq!((from, to), (Point { x: 1, y: 2 }, Point { x: 3, y: 4 })),
+        _ => panic!(),
+    }
+    match Shape::Dot::<u8, NotCopy>(Point { x: 5, y: 6 }).clone() {
+        Shape::Dot(p) => assert_eq!(p, Point { x: 5, y: 6 }),
+        _ => panic!(),
+    }
+
+    let value = 42u8;
+    let raw = Raw { ptr: &value };
+    assert_eq!(raw.clone().ptr, raw.ptr);
+}
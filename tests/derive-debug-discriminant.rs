@@ -0,0 +1,64 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug="discriminant")]
+enum State {
+    Idle,
+    Busy,
+    Ready = 3,
+    Done,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug="discriminant")]
+#[repr(u8)]
+enum Opcode {
+    Nop = 0x00,
+    Halt = 0xff,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug="discriminant")]
+#[repr(i16)]
+enum Offset {
+    Back = -1,
+    #[derivative(Debug(name="Stay"))]
+    __Stay,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug(discriminant="true", qualified="true"))]
+#[repr(u64)]
+enum Large {
+    Max = u64::max_value(),
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    assert_eq!(State::Idle.to_show(), "Idle = 0".to_string());
+    assert_eq!(State::Busy.to_show(), "Busy = 1".to_string());
+    assert_eq!(State::Ready.to_show(), "Ready = 3".to_string());
+    assert_eq!(State::Done.to_show(), "Done = 4".to_string());
+
+    assert_eq!(Opcode::Nop.to_show(), "Nop = 0".to_string());
+    assert_eq!(Opcode::Halt.to_show(), "Halt = 255".to_string());
+
+    assert_eq!(Offset::Back.to_show(), "Back = -1".to_string());
+    assert_eq!(Offset::__Stay.to_show(), "Stay = 0".to_string());
+
+    assert_eq!(Large::Max.to_show(), "Large::Max = 18446744073709551615".to_string());
+}
@@ -0,0 +1,154 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+mod header {
+    #[derive(Derivative)]
+    #[derivative(Debug="flattenable")]
+    pub struct CommonHeader {
+        pub version: u8,
+        #[derivative(Debug="ignore")]
+        pub checksum: u32,
+        #[derivative(Debug(rename="kind"))]
+        pub ty: &'static str,
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(Debug="flattenable")]
+struct Metadata<T> {
+    tags: Vec<T>,
+    #[derivative(Debug(max_items="1"))]
+    extra: Vec<u8>,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug="flattenable")]
+struct Request {
+    #[derivative(Debug="flatten")]
+    header: header::CommonHeader,
+    path: &'static str,
+    #[derivative(Debug(flatten="true"))]
+    metadata: Metadata<&'static str>,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+enum Message {
+    Ping,
+    Data {
+        #[derivative(Debug="flatten")]
+        header: header::CommonHeader,
+        #[derivative(Debug(flatten="true", skip_if="|m| m.tags.is_empty()"))]
+        metadata: Metadata<u8>,
+    },
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Outer {
+    #[derivative(Debug="flatten")]
+    request: Request,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug="flattenable")]
+#[repr(C, packed)]
+struct Packed {
+    a: u8,
+    b: u32,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct WithPacked {
+    #[derivative(Debug="flatten")]
+    packed: Packed,
+}
+
+mod crate_visible {
+    #![deny(unreachable_pub)]
+
+    #[derive(Derivative)]
+    #[derivative(Debug="flattenable")]
+    pub(crate) struct Flattenable {
+        pub(crate) a: u8,
+    }
+
+    #[derive(Derivative)]
+    #[derivative(Debug)]
+    pub(crate) struct Plain {
+        pub(crate) a: u8,
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct WithCrateVisible {
+    #[derivative(Debug="flatten")]
+    inner: crate_visible::Flattenable,
+    plain: crate_visible::Plain,
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+fn header() -> header::CommonHeader {
+    header::CommonHeader { version: 1, checksum: 0, ty: "get" }
+}
+
+#[test]
+fn main() {
+    let request = Request {
+        header: header(),
+        path: "/",
+        metadata: Metadata { tags: vec!["a"], extra: vec![1, 2] },
+    };
+    assert_eq!(
+        request.to_show(),
+        "Request { version: 1, kind: \"get\", path: \"/\", tags: [\"a\"], extra: [1, ... and 1 more] }".to_string()
+    );
+
+    assert_eq!(
+        Outer { request }.to_show(),
+        "Outer { version: 1, kind: \"get\", path: \"/\", tags: [\"a\"], extra: [1, ... and 1 more] }".to_string()
+    );
+
+    assert_eq!(Message::Ping.to_show(), "Ping".to_string());
+    let message = Message::Data {
+        header: header(),
+        metadata: Metadata { tags: vec![], extra: vec![] },
+    };
+    assert_eq!(message.to_show(), "Data { version: 1, kind: \"get\" }".to_string());
+    let message = Message::Data {
+        header: header(),
+        metadata: Metadata { tags: vec![2], extra: vec![] },
+    };
+    assert_eq!(
+        message.to_show(),
+        "Data { version: 1, kind: \"get\", tags: [2], extra: [] }".to_string()
+    );
+
+    assert_eq!(
+        WithPacked { packed: Packed { a: 1, b: 2 } }.to_show(),
+        "WithPacked { a: 1, b: 2 }".to_string()
+    );
+
+    let with_crate_visible = WithCrateVisible {
+        inner: crate_visible::Flattenable { a: 1 },
+        plain: crate_visible::Plain { a: 2 },
+    };
+    assert_eq!(
+        with_crate_visible.to_show(),
+        "WithCrateVisible { a: 1, plain: Plain { a: 2 } }".to_string()
+    );
+}
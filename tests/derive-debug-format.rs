@@ -0,0 +1,55 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Register {
+    #[derivative(Debug(format="{:#010x}"))]
+    value: u32,
+    #[derivative(Debug(format="{:#b}"))]
+    flags: u8,
+    #[derivative(Debug(format="{} ms"))]
+    timeout: u64,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Hash(#[derivative(Debug(format="{0:x} ({0})"))] u16);
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+#[repr(C, packed)]
+struct Packed {
+    #[derivative(Debug(format="{:#x}"))]
+    value: u32,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug(bound="T: std::fmt::LowerHex"))]
+enum Generic<T> {
+    V(#[derivative(Debug(format="{:x}"))] T),
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    assert_eq!(
+        Register { value: 0xbeef, flags: 5, timeout: 100 }.to_show(),
+        "Register { value: 0x0000beef, flags: 0b101, timeout: 100 ms }".to_string()
+    );
+    assert_eq!(Hash(255).to_show(), "Hash(ff (255))".to_string());
+    assert_eq!(Packed { value: 255 }.to_show(), "Packed { value: 0xff }".to_string());
+    assert_eq!(Generic::V(255u8).to_show(), "V(ff)".to_string());
+}
@@ -0,0 +1,57 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::collections::BTreeMap;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Packet {
+    id: u8,
+    #[derivative(Debug(max_items="4"))]
+    data: Vec<u8>,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Slice<'a, T: 'a>(#[derivative(Debug(max_items="2"))] &'a [T]);
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+enum Collections {
+    Map(#[derivative(Debug(max_items="1"))] BTreeMap<u8, &'static str>),
+    Array {
+        #[derivative(Debug(max_items="0"))]
+        array: [u8; 3],
+    },
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    let packet = Packet { id: 1, data: vec![0; 10000] };
+    assert_eq!(packet.to_show(), "Packet { id: 1, data: [0, 0, 0, 0, ... and 9996 more] }".to_string());
+
+    let packet = Packet { id: 1, data: vec![1, 2, 3, 4] };
+    assert_eq!(packet.to_show(), "Packet { id: 1, data: [1, 2, 3, 4] }".to_string());
+
+    assert_eq!(Slice(&[1, 2, 3]).to_show(), "Slice([1, 2, ... and 1 more])".to_string());
+    assert_eq!(Slice::<u8>(&[]).to_show(), "Slice([])".to_string());
+
+    let mut map = BTreeMap::new();
+    map.insert(1, "one");
+    map.insert(2, "two");
+    assert_eq!(Collections::Map(map).to_show(), "Map([(1, \"one\"), ... and 1 more])".to_string());
+    assert_eq!(Collections::Array { array: [1, 2, 3] }.to_show(), "Array { array: [... and 3 more] }".to_string());
+}
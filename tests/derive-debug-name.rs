@@ -0,0 +1,52 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug(name="State"))]
+struct __InnerStateV2 {
+    foo: u8,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug(name="Pair"))]
+struct __Pair(u8, u8);
+
+#[derive(Derivative)]
+#[derivative(Debug(name="Nothing"))]
+struct Unit;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+enum Mode {
+    #[derivative(Debug(name="Fast"))]
+    __Fast,
+    #[derivative(Debug(name="Slow"))]
+    __Slow(u8),
+    #[derivative(Debug(name="Custom"))]
+    __Custom { speed: u8 },
+    Other,
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    assert_eq!(__InnerStateV2 { foo: 42 }.to_show(), "State { foo: 42 }".to_string());
+    assert_eq!(__Pair(1, 2).to_show(), "Pair(1, 2)".to_string());
+    assert_eq!(Unit.to_show(), "Nothing".to_string());
+    assert_eq!(Mode::__Fast.to_show(), "Fast".to_string());
+    assert_eq!(Mode::__Slow(1).to_show(), "Slow(1)".to_string());
+    assert_eq!(Mode::__Custom { speed: 2 }.to_show(), "Custom { speed: 2 }".to_string());
+    assert_eq!(Mode::Other.to_show(), "Other".to_string());
+}
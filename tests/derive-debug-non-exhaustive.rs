@@ -0,0 +1,70 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug="non_exhaustive")]
+struct Foo {
+    foo: u8,
+    #[derivative(Debug="ignore")]
+    bar: u8,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug(non_exhaustive="true"))]
+struct Bar {
+    foo: u8,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug(non_exhaustive="true"))]
+struct Baz(u8, #[derivative(Debug="ignore")] u8);
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+enum C {
+    #[derivative(Debug="non_exhaustive")]
+    V1 {
+        foo: u8,
+        #[derivative(Debug="ignore")]
+        bar: u8,
+    },
+    V2 {
+        foo: u8,
+        #[derivative(Debug="ignore")]
+        bar: u8,
+    },
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    // `finish_non_exhaustive` is only used on recent compilers, see `build.rs`
+    if cfg!(derivative_debug_struct_non_exhaustive) {
+        assert_eq!(Foo { foo: 42, bar: 1 }.to_show(), "Foo { foo: 42, .. }".to_string());
+        assert_eq!(C::V1 { foo: 42, bar: 1 }.to_show(), "V1 { foo: 42, .. }".to_string());
+    } else {
+        assert_eq!(Foo { foo: 42, bar: 1 }.to_show(), "Foo { foo: 42 }".to_string());
+        assert_eq!(C::V1 { foo: 42, bar: 1 }.to_show(), "V1 { foo: 42 }".to_string());
+    }
+
+    if cfg!(derivative_debug_tuple_non_exhaustive) {
+        assert_eq!(Baz(42, 1).to_show(), "Baz(42, ..)".to_string());
+    } else {
+        assert_eq!(Baz(42, 1).to_show(), "Baz(42)".to_string());
+    }
+
+    assert_eq!(Bar { foo: 42 }.to_show(), "Bar { foo: 42 }".to_string());
+    assert_eq!(C::V2 { foo: 42, bar: 1 }.to_show(), "V2 { foo: 42 }".to_string());
+}
@@ -0,0 +1,62 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug="qualified")]
+enum Mode {
+    Fast,
+    Slow(u8),
+    Custom { speed: u8 },
+    #[derivative(Debug(name="Unknown"))]
+    __Unknown,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug(qualified="true", name="Level"))]
+enum __LevelV2 {
+    Low,
+    High,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug(qualified="true", transparent="true"))]
+enum Either {
+    Left(u8),
+    Right(&'static str),
+}
+
+#[derive(Derivative)]
+#[derivative(Debug(qualified="false"))]
+enum Plain {
+    None,
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    assert_eq!(Mode::Fast.to_show(), "Mode::Fast".to_string());
+    assert_eq!(Mode::Slow(1).to_show(), "Mode::Slow(1)".to_string());
+    assert_eq!(Mode::Custom { speed: 2 }.to_show(), "Mode::Custom { speed: 2 }".to_string());
+    assert_eq!(Mode::__Unknown.to_show(), "Mode::Unknown".to_string());
+    assert_eq!(format!("{:#?}", Mode::Slow(1)), "Mode::Slow(\n    1,\n)".to_string());
+
+    assert_eq!(__LevelV2::Low.to_show(), "Level::Low".to_string());
+    assert_eq!(__LevelV2::High.to_show(), "Level::High".to_string());
+
+    assert_eq!(Either::Left(1).to_show(), "1".to_string());
+    assert_eq!(Either::Right("foo").to_show(), "\"foo\"".to_string());
+
+    assert_eq!(Plain::None.to_show(), "None".to_string());
+}
@@ -0,0 +1,58 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Credentials {
+    user: &'static str,
+    #[derivative(Debug="redact")]
+    password: &'static str,
+    #[derivative(Debug(redact="<token>"))]
+    token: &'static str,
+    #[derivative(Debug(redact="false"))]
+    id: u32,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Tuple(u8, #[derivative(Debug="redact")] NoDebug);
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+enum Secret<T> {
+    Key {
+        #[derivative(Debug(redact="{key}"))]
+        key: T,
+    },
+}
+
+struct NoDebug;
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    let credentials = Credentials {
+        user: "root",
+        password: "hunter2",
+        token: "abcd",
+        id: 1,
+    };
+    assert_eq!(
+        credentials.to_show(),
+        "Credentials { user: \"root\", password: ***, token: <token>, id: 1 }".to_string()
+    );
+    assert_eq!(Tuple(1, NoDebug).to_show(), "Tuple(1, ***)".to_string());
+    assert_eq!(Secret::Key { key: NoDebug }.to_show(), "Key { key: {key} }".to_string());
+}
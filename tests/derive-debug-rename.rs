@@ -0,0 +1,43 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Foo {
+    #[derivative(Debug(rename="user_id"))]
+    uid: u32,
+    #[derivative(Debug(rename="type"))]
+    r#type: u8,
+    name: &'static str,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+enum Bar {
+    V {
+        #[derivative(Debug(rename="value"))]
+        val: u8,
+    },
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    assert_eq!(
+        Foo { uid: 42, r#type: 1, name: "foo" }.to_show(),
+        "Foo { user_id: 42, type: 1, name: \"foo\" }".to_string()
+    );
+    assert_eq!(Bar::V { val: 3 }.to_show(), "V { value: 3 }".to_string());
+}
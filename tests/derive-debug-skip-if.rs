@@ -0,0 +1,72 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+fn is_zero(n: &u32) -> bool {
+    *n == 0
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Config {
+    name: &'static str,
+    #[derivative(Debug(skip_if="Option::is_none"))]
+    port: Option<u16>,
+    #[derivative(Debug(skip_if="Option::is_none"))]
+    host: Option<&'static str>,
+    #[derivative(Debug(skip_if="is_zero", format="{:#x}"))]
+    flags: u32,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Credentials {
+    user: &'static str,
+    #[derivative(Debug(redact="true", skip_if="Option::is_none"))]
+    token: Option<String>,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Tuple(u8, #[derivative(Debug(skip_if="Vec::is_empty"))] Vec<u8>);
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+#[repr(C, packed)]
+struct Packed {
+    #[derivative(Debug(skip_if="is_zero"))]
+    value: u32,
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    let config = Config { name: "foo", port: None, host: None, flags: 0 };
+    assert_eq!(config.to_show(), "Config { name: \"foo\" }".to_string());
+
+    let config = Config { name: "foo", port: Some(80), host: None, flags: 16 };
+    assert_eq!(config.to_show(), "Config { name: \"foo\", port: Some(80), flags: 0x10 }".to_string());
+
+    let credentials = Credentials { user: "foo", token: None };
+    assert_eq!(credentials.to_show(), "Credentials { user: \"foo\" }".to_string());
+
+    let credentials = Credentials { user: "foo", token: Some("secret".to_string()) };
+    assert_eq!(credentials.to_show(), "Credentials { user: \"foo\", token: *** }".to_string());
+
+    assert_eq!(Tuple(1, vec![]).to_show(), "Tuple(1)".to_string());
+    assert_eq!(Tuple(1, vec![2]).to_show(), "Tuple(1, [2])".to_string());
+
+    assert_eq!(Packed { value: 0 }.to_show(), "Packed".to_string());
+    assert_eq!(Packed { value: 1 }.to_show(), "Packed { value: 1 }".to_string());
+}
@@ -20,6 +20,29 @@ enum C {
     Bar(u8),
 }
 
+#[derive(Derivative)]
+#[derivative(Debug="transparent")]
+enum Value {
+    Int(i64),
+    Str(&'static str),
+    Named { value: bool },
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+enum D {
+    #[derivative(Debug="transparent")]
+    Hex(#[derivative(Debug(format="{:#x}"))] u8),
+    Pair(u8, u8),
+    #[derivative(Debug="transparent")]
+    Skip(#[derivative(Debug="ignore")] (), u8),
+}
+
+#[derive(Derivative)]
+#[derivative(Debug="transparent")]
+#[repr(C, packed)]
+struct Packed(u32);
+
 trait ToDebug {
     fn to_show(&self) -> String;
 }
@@ -36,4 +59,11 @@ fn main() {
     assert_eq!(B([42]).to_show(), "[42]".to_string());
     assert_eq!(C::Foo(42).to_show(), "Foo(42)".to_string());
     assert_eq!(C::Bar(42).to_show(), "42".to_string());
+    assert_eq!(Value::Int(3).to_show(), "3".to_string());
+    assert_eq!(Value::Str("foo").to_show(), "\"foo\"".to_string());
+    assert_eq!(Value::Named { value: true }.to_show(), "true".to_string());
+    assert_eq!(D::Hex(255).to_show(), "0xff".to_string());
+    assert_eq!(D::Pair(1, 2).to_show(), "Pair(1, 2)".to_string());
+    assert_eq!(D::Skip((), 2).to_show(), "2".to_string());
+    assert_eq!(Packed(42).to_show(), "42".to_string());
 }
@@ -30,6 +30,14 @@ struct WithOption<T> {
     foo: Option<T>,
 }
 
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default)]
+struct WithValue<T, U> {
+    foo: U,
+    #[derivative(Default(value="None"))]
+    bar: Option<T>,
+}
+
 struct NonDefault;
 
 #[test]
@@ -39,4 +47,5 @@ fn main() {
     assert_eq!(Foo::new(), Foo { foo: 0u8, bar: 0xffu8 });
     assert_eq!(Bar::new(), Bar { foo: 0u8, bar: 0xffu8 });
     WithOption::<NonDefault>::default();
+    assert!(WithValue::<NonDefault, u8>::default().bar.is_none());
 }
@@ -0,0 +1,69 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default="const")]
+struct Foo {
+    #[derivative(Default(value="1"))]
+    foo: u8,
+    #[derivative(Default(value="\"bar\""))]
+    bar: &'static str,
+    #[derivative(Default(value="Vec::new()"))]
+    baz: Vec<u8>,
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default(const="true", new="true"))]
+struct Bar (
+    #[derivative(Default(value="42"))]
+    u8,
+);
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default(const="true"))]
+enum Baz {
+    A(u8),
+    #[derivative(Default)]
+    B {
+        #[derivative(Default(value="Some(2)"))]
+        b: Option<u8>,
+    },
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default(const="true", new="args"))]
+struct Qux<T> {
+    #[derivative(Default(value="None"))]
+    qux: Option<T>,
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default(const="true", new="args"))]
+struct Quux {
+    a: u8,
+    #[derivative(Default(value="\"quux\""))]
+    b: &'static str,
+}
+
+struct NonDefault;
+
+static FOO: Foo = Foo::DEFAULT;
+static BAR: Bar = Bar::new();
+const QUX: Qux<NonDefault> = Qux::new();
+const QUUX: Quux = Quux::new(1);
+
+#[test]
+fn main() {
+    assert_eq!(FOO, Foo { foo: 1, bar: "bar", baz: vec![] });
+    assert_eq!(Foo::default(), FOO);
+    assert_eq!(BAR, Bar(42));
+    assert_eq!(Baz::DEFAULT, Baz::B { b: Some(2) });
+    assert_eq!(Baz::default(), Baz::DEFAULT);
+    assert_ne!(Baz::A(2), Baz::DEFAULT);
+    assert!(QUX.qux.is_none());
+    assert_eq!(QUUX, Quux { a: 1, b: "quux" });
+    assert_eq!(Quux::default(), Quux { a: 0, b: "quux" });
+}
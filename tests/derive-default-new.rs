@@ -0,0 +1,53 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default(new="args"))]
+struct Foo {
+    foo: u8,
+    #[derivative(Default(value="42"))]
+    bar: u8,
+    baz: String,
+    #[derivative(Default="skip")]
+    qux: Vec<u8>,
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default(new="args"))]
+struct Bar (
+    #[derivative(Default(value="42"))]
+    u8,
+    u8,
+);
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default(new="args"))]
+struct Unit;
+
+struct NonDefault;
+
+#[derive(Derivative)]
+#[derivative(Default(new="args"))]
+struct Generic<T, U> {
+    required: T,
+    #[derivative(Default="skip")]
+    skipped: Option<U>,
+}
+
+#[test]
+fn main() {
+    assert_eq!(
+        Foo::new(1, "baz".to_string()),
+        Foo { foo: 1, bar: 42, baz: "baz".to_string(), qux: vec![] }
+    );
+    assert_eq!(Foo::default(), Foo { foo: 0, bar: 42, baz: String::new(), qux: vec![] });
+    assert_eq!(Bar::new(1), Bar(42, 1));
+    assert_eq!(Unit::new(), Unit);
+
+    let generic = Generic::<NonDefault, u8>::new(NonDefault);
+    let Generic { required: NonDefault, skipped } = generic;
+    assert!(skipped.is_none());
+}
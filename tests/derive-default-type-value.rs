@@ -0,0 +1,48 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default(value="Self::with_capacity(16)", new="true"))]
+struct Buffer<T> {
+    items: Vec<T>,
+    capacity: usize,
+}
+
+impl<T> Buffer<T> {
+    fn with_capacity(capacity: usize) -> Self {
+        Buffer { items: Vec::with_capacity(capacity), capacity }
+    }
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default(value="Shape::Square(1)"))]
+enum Shape {
+    Circle(u8),
+    Square(u8),
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default(value="Limits { min: 1, max: 10 }", const="true"))]
+struct Limits {
+    min: u8,
+    max: u8,
+}
+
+struct NonDefault;
+
+#[test]
+fn main() {
+    let buffer = Buffer::<NonDefault>::default();
+    assert_eq!(buffer.capacity, 16);
+    assert!(buffer.items.is_empty());
+    assert_eq!(Buffer::<NonDefault>::new().capacity, 16);
+
+    assert_eq!(Shape::default(), Shape::Square(1));
+    assert_ne!(Shape::default(), Shape::Circle(1));
+
+    assert_eq!(Limits::default(), Limits { min: 1, max: 10 });
+    assert_eq!(Limits::DEFAULT, Limits { min: 1, max: 10 });
+}
@@ -0,0 +1,60 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+mod defaults {
+    pub fn answer() -> u8 {
+        42
+    }
+
+    pub const fn name() -> &'static str {
+        "name"
+    }
+
+    pub fn none<T>() -> Option<T> {
+        None
+    }
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default)]
+struct Foo {
+    #[derivative(Default(with="defaults::answer"))]
+    foo: u8,
+    #[derivative(Default(with="String::new"))]
+    bar: String,
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default)]
+struct Bar(#[derivative(Default(with="defaults::answer"))] u8);
+
+struct NonDefault;
+
+#[derive(Derivative)]
+#[derivative(Default(new="args"), Builder)]
+struct Generic<T> {
+    #[derivative(Default(with="defaults::none"))]
+    value: Option<T>,
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default="const")]
+struct Const {
+    #[derivative(Default(with="defaults::name"))]
+    name: &'static str,
+}
+
+#[test]
+fn main() {
+    assert_eq!(Foo::default(), Foo { foo: 42, bar: String::new() });
+    assert_eq!(Bar::default(), Bar(42));
+
+    assert!(Generic::<NonDefault>::default().value.is_none());
+    assert!(Generic::<NonDefault>::new().value.is_none());
+    assert!(Generic::<NonDefault>::builder().build().unwrap().value.is_none());
+
+    assert_eq!(Const::DEFAULT, Const { name: "name" });
+}
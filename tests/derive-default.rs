@@ -58,6 +58,23 @@ enum Enum2 {
     B,
 }
 
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default(new="true"))]
+enum Enum3 {
+    #[allow(dead_code)]
+    A(u8),
+    #[derivative(Default)]
+    B {
+        b: u8,
+        #[derivative(Default(value="42"))]
+        c: u8,
+        #[derivative(Default(value="NoDefault"))]
+        d: NoDefault,
+    },
+    #[allow(dead_code)]
+    C,
+}
+
 #[derive(Debug, Derivative, PartialEq)]
 #[derivative(Default)]
 struct A(#[derivative(Default(value="NoDefault"))] NoDefault);
@@ -75,4 +92,6 @@ fn main() {
     assert_eq!(A::default(), A(NoDefault));
     assert_eq!(Enum1::default(), Enum1::B);
     assert_eq!(Enum2::default(), Enum2::A);
+    assert_eq!(Enum3::default(), Enum3::B { b: 0, c: 42, d: NoDefault });
+    assert_eq!(Enum3::new(), Enum3::B { b: 0, c: 42, d: NoDefault });
 }
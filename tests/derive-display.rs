@@ -0,0 +1,86 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Display="{name} at {line}:{column}")]
+struct Location {
+    name: &'static str,
+    line: u32,
+    column: u32,
+}
+
+#[derive(Derivative)]
+#[derivative(Display(fmt="#{0:04}"))]
+struct Id(u32);
+
+#[derive(Derivative)]
+#[derivative(Display="{} + {} = {0}{1}")]
+struct Concat(&'static str, &'static str);
+
+#[derive(Derivative)]
+#[derivative(Display="unit")]
+struct Unit;
+
+#[derive(Derivative)]
+#[derivative(Display)]
+enum Error {
+    #[derivative(Display="file not found: {path}")]
+    NotFound { path: &'static str },
+    #[derivative(Display="invalid digit {0:?} at {1}")]
+    InvalidDigit(char, usize),
+    #[derivative(Display="unexpected end of file")]
+    Eof,
+    #[derivative(Display="{{escaped}} {type}")]
+    Raw { r#type: u8 },
+}
+
+#[derive(Derivative)]
+#[derivative(Display="[{value:>width$}]")]
+struct Padded {
+    value: u32,
+    width: usize,
+}
+
+#[derive(Derivative)]
+#[derivative(Display="{0}")]
+enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+#[derive(Derivative)]
+#[derivative(Display="{value}")]
+struct OnlyValue<T, U> {
+    value: T,
+    _ignored: U,
+}
+
+struct NoDisplay;
+
+#[derive(Derivative)]
+#[derivative(Display="{value}")]
+#[repr(C, packed)]
+struct Packed {
+    value: u32,
+}
+
+#[test]
+fn main() {
+    let location = Location { name: "main.rs", line: 4, column: 2 };
+    assert_eq!(location.to_string(), "main.rs at 4:2");
+    assert_eq!(Id(42).to_string(), "#0042");
+    assert_eq!(Concat("a", "b").to_string(), "a + b = ab");
+    assert_eq!(Unit.to_string(), "unit");
+    assert_eq!(Error::NotFound { path: "foo" }.to_string(), "file not found: foo");
+    assert_eq!(Error::InvalidDigit('x', 3).to_string(), "invalid digit 'x' at 3");
+    assert_eq!(Error::Eof.to_string(), "unexpected end of file");
+    assert_eq!(Error::Raw { r#type: 1 }.to_string(), "{escaped} 1");
+    assert_eq!(Padded { value: 42, width: 5 }.to_string(), "[   42]");
+    assert_eq!(Either::Left::<u8, &str>(1).to_string(), "1");
+    assert_eq!(Either::Right::<u8, &str>("foo").to_string(), "foo");
+    assert_eq!(OnlyValue { value: 1, _ignored: NoDisplay }.to_string(), "1");
+    assert_eq!(Packed { value: 42 }.to_string(), "42");
+}
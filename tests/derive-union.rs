@@ -0,0 +1,75 @@
+#![allow(clippy::blacklisted_name)]
+
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::fmt;
+
+#[derive(Derivative)]
+#[derivative(Clone, Copy, Debug)]
+union Value {
+    int: u32,
+    float: f32,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone, Copy, Debug(name="Bits"))]
+union __RawBits<T: Copy> {
+    value: T,
+    bytes: [u8; 4],
+}
+
+fn fmt_tagged(tagged: &Tagged, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "Tagged({:#x})", unsafe { tagged.bits })
+}
+
+#[derive(Derivative)]
+#[derivative(Clone, Copy, Debug(format_with="fmt_tagged"))]
+union Tagged {
+    bits: u32,
+    float: f32,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug(format_with="|s: &Self, f: &mut fmt::Formatter| write!(f, \"Foo<{}>\", s.0)"))]
+struct Foo(u8);
+
+#[derive(Derivative)]
+#[derivative(Debug(format_with="|_, f| f.write_str(\"NotDebug\")"))]
+enum NotDebug<T> {
+    A(T),
+}
+
+struct NoDebug;
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    let value = Value { int: 42 };
+    let copy = value;
+    let clone = value.clone();
+    assert_eq!(unsafe { copy.int }, 42);
+    assert_eq!(unsafe { clone.int }, 42);
+    assert_eq!(value.to_show(), "Value { .. }".to_string());
+
+    let bits = __RawBits { value: 1u32 };
+    assert_eq!(unsafe { bits.clone().value }, 1);
+    assert_eq!(bits.to_show(), "Bits { .. }".to_string());
+
+    assert_eq!(Tagged { bits: 255 }.clone().to_show(), "Tagged(0xff)".to_string());
+
+    assert_eq!(Foo(1).to_show(), "Foo<1>".to_string());
+    assert_eq!(NotDebug::A(NoDebug).to_show(), "NotDebug".to_string());
+}
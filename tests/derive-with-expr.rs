@@ -0,0 +1,98 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+mod my_mod {
+    use std::hash::{Hash, Hasher};
+
+    pub fn hash_by<T, K: Hash, H: Hasher>(key: impl Fn(&T) -> K) -> impl Fn(&T, &mut H) {
+        move |value, state| key(value).hash(state)
+    }
+}
+
+#[derive(Debug)]
+struct Item {
+    id: u32,
+    label: &'static str,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+struct Entry {
+    #[derivative(
+        Debug(format_with="|s, f| write!(f, \"<{}>\", s)"),
+        PartialEq(compare_with="|a, b| a.eq_ignore_ascii_case(b)"),
+        PartialOrd(compare_with="|a, b| Some(a.to_lowercase().cmp(&b.to_lowercase()))"),
+        Ord(compare_with="|a, b| a.to_lowercase().cmp(&b.to_lowercase())"),
+        Hash(hash_with="|s, state| s.to_lowercase().hash(state)"),
+    )]
+    name: String,
+    #[derivative(
+        Clone(clone_with="|item| Item { id: item.id, label: \"cloned\" }"),
+        Debug(skip_if="|item| item.id == 0"),
+        Hash(hash_with="my_mod::hash_by(|item: &Item| item.id)"),
+        PartialEq(compare_with="|a, b| a.id == b.id"),
+        PartialOrd(compare_with="|a, b| a.id.partial_cmp(&b.id)"),
+        Ord(compare_with="|a, b| a.id.cmp(&b.id)"),
+    )]
+    item: Item,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone, PartialEq)]
+#[repr(C, packed)]
+struct Packed {
+    #[derivative(
+        Clone(clone_with="|x| x + 1"),
+        PartialEq(compare_with="|a: &u32, b: &u32| a % 10 == b % 10"),
+    )]
+    value: u32,
+}
+
+fn entry(name: &str, id: u32, label: &'static str) -> Entry {
+    Entry {
+        name: name.to_string(),
+        item: Item { id, label },
+    }
+}
+
+fn hash<T: Hash>(t: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    t.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn main() {
+    assert_eq!(
+        format!("{:?}", entry("foo", 1, "a")),
+        "Entry { name: <foo>, item: Item { id: 1, label: \"a\" } }"
+    );
+    assert_eq!(format!("{:?}", entry("foo", 0, "a")), "Entry { name: <foo> }");
+
+    assert_eq!(entry("foo", 1, "a").clone().item.label, "cloned");
+
+    assert!(entry("foo", 1, "a") == entry("FOO", 1, "b"));
+    assert!(entry("foo", 1, "a") != entry("bar", 1, "a"));
+    assert!(entry("foo", 1, "a") != entry("foo", 2, "a"));
+
+    assert_eq!(hash(&entry("foo", 1, "a")), hash(&entry("FOO", 1, "b")));
+    assert_ne!(hash(&entry("foo", 1, "a")), hash(&entry("foo", 2, "a")));
+
+    assert_eq!(
+        entry("foo", 1, "a").partial_cmp(&entry("FOO", 1, "b")),
+        Some(Ordering::Equal)
+    );
+    assert_eq!(entry("a", 2, "a").cmp(&entry("B", 1, "a")), Ordering::Less);
+    assert_eq!(entry("a", 2, "a").cmp(&entry("A", 1, "a")), Ordering::Greater);
+
+    let packed = Packed { value: 41 }.clone();
+    assert_eq!({ packed.value }, 42);
+    assert!(Packed { value: 2 } == Packed { value: 12 });
+}